@@ -0,0 +1,93 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Trains the naive Bayes language classifier from data/classifier/*.txt and
+// writes the model to $OUT_DIR/classifier_model.rs, so nothing is trained at
+// runtime. Each file holds samples of one type, named by its extension.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{env, fs};
+
+#[path = "src/tokens.rs"]
+#[allow(dead_code)]
+mod tokens;
+
+const TRAINING_DIR: &str = "data/classifier";
+
+fn main() {
+    println!("cargo:rerun-if-changed={TRAINING_DIR}");
+    println!("cargo:rerun-if-changed=src/tokens.rs");
+
+    let mut files: Vec<_> = fs::read_dir(TRAINING_DIR)
+        .expect("missing classifier training data")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "txt"))
+        .collect();
+    files.sort();
+
+    let mut labels = Vec::new();
+    let mut counts: Vec<BTreeMap<String, u32>> = Vec::new();
+    for path in &files {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let label = path.file_stem().unwrap().to_str().unwrap().to_string();
+        // a checkout with CRLF line endings trains the same model
+        let text = fs::read_to_string(path).unwrap().replace("\r\n", "\n");
+
+        // samples are separated by `<<<>>>` lines. Features count once per
+        // sample, so a long sample can't drown out the others with a few
+        // repeated tokens.
+        let mut class_counts = BTreeMap::new();
+        for sample in text.split("\n<<<>>>\n") {
+            let features: BTreeSet<String> = tokens::features(sample).into_iter().collect();
+            for feature in features {
+                *class_counts.entry(feature).or_insert(0) += 1;
+            }
+        }
+        labels.push(label);
+        counts.push(class_counts);
+    }
+
+    let vocabulary: BTreeSet<&String> = counts.iter().flat_map(|c| c.keys()).collect();
+    let vocabulary_size = vocabulary.len() as f64;
+
+    // Laplace smoothing; entries store the difference from the unseen score
+    // so scoring only touches the classes a feature actually occurred in
+    let unseen: Vec<f64> = counts
+        .iter()
+        .map(|c| {
+            let total: u32 = c.values().sum();
+            -(total as f64 + vocabulary_size).ln()
+        })
+        .collect();
+
+    let mut out = String::new();
+    writeln!(out, "pub static LABELS: &[&str] = &{labels:?};").unwrap();
+    let unseen_literals: Vec<String> = unseen.iter().map(|u| format!("{:?}", *u as f32)).collect();
+    writeln!(
+        out,
+        "pub static UNSEEN: &[f32] = &[{}];",
+        unseen_literals.join(", ")
+    )
+    .unwrap();
+    // ln(2) and friends show up as plain numbers
+    writeln!(out, "#[allow(clippy::approx_constant)]").unwrap();
+    writeln!(out, "pub static VOCABULARY: &[(&str, &[(u8, f32)])] = &[").unwrap();
+    for feature in vocabulary {
+        let entries: Vec<String> = counts
+            .iter()
+            .enumerate()
+            .filter_map(|(class, c)| {
+                let count = *c.get(feature)?;
+                let delta = ((count + 1) as f64).ln();
+                Some(format!("({class}, {:?})", delta as f32))
+            })
+            .collect();
+        writeln!(out, "    ({feature:?}, &[{}]),", entries.join(", ")).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("classifier_model.rs");
+    fs::write(dest, out).unwrap();
+}
@@ -1,77 +1,551 @@
 // Copyright (c) 2025 balintb - https://github.com/balintb/c2f
 // Licensed under the MIT License
 
+use crate::compress::Compression;
+use crate::detect::ContentType;
+use crate::encoding::{Bom, Encoding, Eol};
+use crate::history::parse_age;
+use crate::output::parse_mode;
 use std::env;
+use std::time::Duration;
 
+#[derive(Clone, Default)]
 pub struct Args {
     pub filename: Option<String>,
     pub append: bool,
     pub quiet: bool,
     pub detect: Option<bool>, // None means use config default
+    pub content_type: Option<ContentType>,
+    pub list_types: bool,
     pub append_ext: bool,
+    pub redact: bool,
+    pub mode: Option<u32>,
+    pub eol: Option<Eol>,
+    pub bom: Option<Bom>,
+    pub encoding: Option<Encoding>,
+    pub compress: Option<Compression>,
+    pub encrypt: bool,
+    pub recipients: Vec<String>,
+    pub decrypt: bool,
+    pub identity: Option<String>,
+    pub stdout: bool,
+    pub apply: bool,
+    pub json: bool,
+    pub interval: Option<Duration>,
+    pub extract_code: Option<CodeBlocks>,
+    pub split: bool,
+    pub force: bool,
+    pub convert: bool,
+    pub validate: Option<Validation>,
+    pub sanitize: bool,
+    pub command: Command,
 }
 
+/// Which fenced code blocks --extract-code saves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CodeBlocks {
+    All,
+    // 1-based, in document order
+    Only(usize),
+}
+
+/// What --validate does with content that doesn't parse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Validation {
+    Fail,
+    Warn,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Save,
+    Paste,
+    Watch,
+    History(HistoryCommand),
+    Config(ConfigCommand),
+    // files to classify; stdin when empty
+    Detect(Vec<String>),
+    Completions(Shell),
+    Man,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistoryCommand {
+    List,
+    Search(String),
+    Restore(u64),
+    Prune(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigCommand {
+    Path,
+    Show,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+pub struct OptionSpec {
+    pub short: Option<char>,
+    pub long: &'static str,
+    pub value: Option<&'static str>,
+    // the value may be left out, and is only taken inline (--name=value)
+    pub optional: bool,
+    pub help: &'static str,
+    // subcommands the option applies to; empty for all of them
+    pub commands: &'static [&'static str],
+    // another option it only makes sense with
+    pub requires: Option<&'static str>,
+}
+
+impl OptionSpec {
+    const fn only(self, commands: &'static [&'static str]) -> Self {
+        OptionSpec { commands, ..self }
+    }
+
+    const fn short(self, short: char) -> Self {
+        OptionSpec {
+            short: Some(short),
+            ..self
+        }
+    }
+
+    const fn requiring(self, option: &'static str) -> Self {
+        OptionSpec {
+            requires: Some(option),
+            ..self
+        }
+    }
+
+    /// The value name when the value can be the next argument.
+    pub fn separate_value(&self) -> Option<&'static str> {
+        self.value.filter(|_| !self.optional)
+    }
+}
+
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+const fn flag(short: Option<char>, long: &'static str, help: &'static str) -> OptionSpec {
+    OptionSpec {
+        short,
+        long,
+        value: None,
+        optional: false,
+        help,
+        commands: &[],
+        requires: None,
+    }
+}
+
+const fn opt(long: &'static str, value: &'static str, help: &'static str) -> OptionSpec {
+    OptionSpec {
+        short: None,
+        long,
+        value: Some(value),
+        optional: false,
+        help,
+        commands: &[],
+        requires: None,
+    }
+}
+
+const fn opt_inline(long: &'static str, value: &'static str, help: &'static str) -> OptionSpec {
+    OptionSpec {
+        short: None,
+        long,
+        value: Some(value),
+        optional: true,
+        help,
+        commands: &[],
+        requires: None,
+    }
+}
+
+// `save` is also what runs without a subcommand
+const SAVE: &[&str] = &["save"];
+const SAVING: &[&str] = &["save", "watch"];
+
+pub const OPTIONS: &[OptionSpec] = &[
+    flag(Some('a'), "append", "Append to file instead of overwriting").only(SAVING),
+    flag(Some('q'), "quiet", "Suppress all output"),
+    flag(
+        Some('e'),
+        "append-ext",
+        "Add detected extension to specified filename",
+    )
+    .only(SAVING),
+    opt(
+        "detect",
+        "<bool>",
+        "Override content detection (true/false)",
+    )
+    .only(SAVING),
+    opt(
+        "type",
+        "<type>",
+        "Force the content type, by name or extension (e.g. yaml, py)",
+    )
+    .short('t')
+    .only(SAVING),
+    flag(None, "list-types", "List supported content types and exit"),
+    flag(None, "redact", "Replace detected secrets with [REDACTED]").only(SAVING),
+    opt(
+        "mode",
+        "<octal>",
+        "Permissions for the written file, e.g. 600",
+    )
+    .only(SAVING),
+    opt(
+        "compress",
+        "<alg>",
+        "Compress the file: gzip, zstd (or use a .gz/.zst name)",
+    )
+    .only(SAVING),
+    flag(None, "encrypt", "Encrypt the file with age (adds .age)").only(SAVING),
+    opt(
+        "recipient",
+        "<key>",
+        "Encrypt to an age public key instead of a passphrase",
+    )
+    .only(SAVING),
+    flag(None, "decrypt", "Decrypt [filename] back to the clipboard").only(SAVE),
+    opt("identity", "<file>", "age identity file used by --decrypt")
+        .only(SAVE)
+        .requiring("decrypt"),
+    flag(None, "stdout", "With --decrypt, print to stdout instead")
+        .only(SAVE)
+        .requiring("decrypt"),
+    flag(None, "json", "With detect, print results as JSON").only(&["detect"]),
+    flag(
+        None,
+        "apply",
+        "Apply a diff on the clipboard to the current directory",
+    )
+    .only(SAVE),
+    opt_inline(
+        "extract-code",
+        "<n>",
+        "Save the fenced code blocks in Markdown, or only block n",
+    )
+    .only(SAVE),
+    flag(
+        None,
+        "split",
+        "Write each file of a bundle with path headers into [filename] dir",
+    )
+    .only(SAVE),
+    flag(None, "force", "With --split, overwrite existing files")
+        .only(SAVE)
+        .requiring("split"),
+    flag(
+        None,
+        "convert",
+        "Convert data or tables to the format of [filename]",
+    )
+    .only(SAVE),
+    opt_inline(
+        "validate",
+        "warn",
+        "Refuse to save data that doesn't parse, or only warn",
+    )
+    .only(SAVING),
+    flag(
+        None,
+        "sanitize",
+        "Replace smart quotes, odd spaces and ligatures in text",
+    )
+    .only(SAVING),
+    opt(
+        "eol",
+        "<mode>",
+        "Line endings for text: lf, crlf, native, keep",
+    )
+    .only(SAVING),
+    opt("bom", "<mode>", "Byte order mark: add, strip, keep").only(SAVING),
+    opt(
+        "encoding",
+        "<enc>",
+        "Text encoding: utf-8, utf-16le, utf-16be, latin1",
+    )
+    .only(SAVING),
+    opt(
+        "interval",
+        "<secs>",
+        "With watch, seconds between clipboard checks (default: 1)",
+    )
+    .only(&["watch"]),
+    opt(
+        "older-than",
+        "<age>",
+        "With history prune, age of entries to remove, e.g. 30d",
+    )
+    .only(&["history"]),
+    flag(Some('h'), "help", "Print help"),
+    flag(Some('V'), "version", "Print version"),
+];
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "save",
+        args: "[filename]",
+        help: "Write clipboard contents to a file (default)",
+    },
+    CommandSpec {
+        name: "paste",
+        args: "",
+        help: "Print clipboard contents to stdout",
+    },
+    CommandSpec {
+        name: "watch",
+        args: "[filename]",
+        help: "Save every new clipboard entry until interrupted",
+    },
+    CommandSpec {
+        name: "history",
+        args: "[list | search <term> | restore <id> | prune --older-than <age>]",
+        help: "List, search, restore or prune saved entries",
+    },
+    CommandSpec {
+        name: "config",
+        args: "[path | show]",
+        help: "Print the config file location or contents",
+    },
+    CommandSpec {
+        name: "detect",
+        args: "[files...]",
+        help: "Print the detected type of files or stdin",
+    },
+    CommandSpec {
+        name: "completions",
+        args: "<bash | zsh | fish>",
+        help: "Print a shell completion script",
+    },
+    CommandSpec {
+        name: "man",
+        args: "",
+        help: "Print the man page",
+    },
+];
+
 impl Args {
     pub fn parse() -> Result<Self, ArgsError> {
-        let args: Vec<String> = env::args().collect();
-        let mut parsed = Args {
-            filename: None,
-            append: false,
-            quiet: false,
-            detect: None,
-            append_ext: false,
-        };
+        Self::parse_from(env::args().collect())
+    }
+
+    pub fn parse_from(args: Vec<String>) -> Result<Self, ArgsError> {
+        let mut parsed = Args::default();
+        let mut positionals: Vec<String> = Vec::new();
+        let mut older_than = None;
+        let mut seen: Vec<&OptionSpec> = Vec::new();
+        // positionals after `--` are never subcommands
+        let mut terminator_at = None;
 
         let mut i = 1;
         while i < args.len() {
             let arg = &args[i];
-            match arg.as_str() {
-                "-h" | "--help" => return Err(ArgsError::Help),
-                "-V" | "--version" => return Err(ArgsError::Version),
-                "-a" | "--append" => parsed.append = true,
-                "-q" | "--quiet" => parsed.quiet = true,
-                "-e" | "--append-ext" => parsed.append_ext = true,
-                arg if arg.starts_with("--detect=") => {
-                    let value = arg.strip_prefix("--detect=").unwrap();
-                    match value {
-                        "true" => parsed.detect = Some(true),
-                        "false" => parsed.detect = Some(false),
-                        _ => {
-                            return Err(ArgsError::InvalidValue(
-                                "--detect".to_string(),
-                                value.to_string(),
-                            ))
-                        }
-                    }
-                }
-                arg if arg.starts_with("-") && !arg.starts_with("--") && arg.len() > 2 => {
-                    // Handle combined short flags like -qa, -qe, -aqe
-                    for ch in arg[1..].chars() {
-                        match ch {
-                            'a' => parsed.append = true,
-                            'q' => parsed.quiet = true,
-                            'e' => parsed.append_ext = true,
-                            'h' => return Err(ArgsError::Help),
-                            'V' => return Err(ArgsError::Version),
-                            _ => return Err(ArgsError::UnknownFlag(format!("-{ch}"))),
-                        }
+            if terminator_at.is_some() || arg == "-" || !arg.starts_with('-') {
+                positionals.push(arg.to_string());
+            } else if arg == "--" {
+                terminator_at = Some(positionals.len());
+            } else if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (long, None),
+                };
+                let spec = OPTIONS
+                    .iter()
+                    .find(|spec| spec.long == name)
+                    .ok_or_else(|| ArgsError::UnknownFlag(arg.to_string()))?;
+
+                let value = match (spec.value, inline) {
+                    (Some(_), Some(value)) => Some(value.to_string()),
+                    (Some(_), None) if spec.optional => None,
+                    (Some(value_name), None) => {
+                        i += 1;
+                        let value = args.get(i).ok_or_else(|| {
+                            ArgsError::MissingArgument(format!("--{name} {value_name}"))
+                        })?;
+                        Some(value.to_string())
                     }
-                }
-                arg if arg.starts_with('-') => return Err(ArgsError::UnknownFlag(arg.to_string())),
-                _ => {
-                    if parsed.filename.is_none() {
-                        parsed.filename = Some(arg.to_string());
+                    (None, Some(_)) => return Err(ArgsError::UnknownFlag(arg.to_string())),
+                    (None, None) => None,
+                };
+                parsed.apply(name, value.as_deref(), &mut older_than)?;
+                seen.push(spec);
+            } else {
+                // Handle combined short flags like -qa, -qe, -aqe; an option
+                // taking a value ends the group (-qtpy, -qt py)
+                for (pos, ch) in arg.char_indices().skip(1) {
+                    let spec = OPTIONS
+                        .iter()
+                        .find(|spec| spec.short == Some(ch))
+                        .ok_or_else(|| ArgsError::UnknownFlag(format!("-{ch}")))?;
+                    seen.push(spec);
+                    let Some(value_name) = spec.value else {
+                        parsed.apply(spec.long, None, &mut older_than)?;
+                        continue;
+                    };
+
+                    let inline = &arg[pos + ch.len_utf8()..];
+                    let value = if inline.is_empty() {
+                        i += 1;
+                        args.get(i).ok_or_else(|| {
+                            ArgsError::MissingArgument(format!("-{ch} {value_name}"))
+                        })?
                     } else {
-                        return Err(ArgsError::TooManyArgs);
-                    }
+                        inline
+                    };
+                    parsed.apply(spec.long, Some(value), &mut older_than)?;
+                    break;
                 }
             }
             i += 1;
         }
 
+        let is_command = terminator_at != Some(0)
+            && positionals
+                .first()
+                .map_or(false, |first| COMMANDS.iter().any(|c| c.name == first));
+        let is_prune = is_command
+            && positionals[0] == "history"
+            && positionals.get(1).map(String::as_str) == Some("prune");
+        if older_than.is_some() && !is_prune {
+            return Err(ArgsError::UnknownFlag("--older-than".to_string()));
+        }
+        let command = if is_command { &positionals[0] } else { "save" };
+        check_options(&seen, command)?;
+        if !is_command {
+            return parsed.with_filename(positionals);
+        }
+
+        let rest = positionals.split_off(1);
+        let command = positionals.remove(0);
+
+        parsed.command = match command.as_str() {
+            "save" => return parsed.with_filename(rest),
+            "watch" => {
+                parsed.command = Command::Watch;
+                return parsed.with_filename(rest);
+            }
+            "paste" => no_args(rest, Command::Paste)?,
+            "detect" => Command::Detect(rest),
+            "man" => no_args(rest, Command::Man)?,
+            "history" => Command::History(parse_history(&rest, older_than)?),
+            "config" => Command::Config(match single_arg(&rest)? {
+                None | Some("path") => ConfigCommand::Path,
+                Some("show") => ConfigCommand::Show,
+                Some(other) => return Err(invalid("config", other)),
+            }),
+            "completions" => Command::Completions(match single_arg(&rest)? {
+                Some("bash") => Shell::Bash,
+                Some("zsh") => Shell::Zsh,
+                Some("fish") => Shell::Fish,
+                Some(other) => return Err(invalid("completions", other)),
+                None => return Err(ArgsError::MissingArgument("<shell>".to_string())),
+            }),
+            _ => unreachable!("checked against COMMANDS"),
+        };
         Ok(parsed)
     }
 
+    fn apply(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        older_than: &mut Option<String>,
+    ) -> Result<(), ArgsError> {
+        let value = value.unwrap_or_default();
+        match name {
+            "help" => return Err(ArgsError::Help),
+            "version" => return Err(ArgsError::Version),
+            "append" => self.append = true,
+            "quiet" => self.quiet = true,
+            "append-ext" => self.append_ext = true,
+            "redact" => self.redact = true,
+            "encrypt" => self.encrypt = true,
+            "decrypt" => self.decrypt = true,
+            "stdout" => self.stdout = true,
+            "json" => self.json = true,
+            "apply" => self.apply = true,
+            "split" => self.split = true,
+            "force" => self.force = true,
+            "convert" => self.convert = true,
+            "sanitize" => self.sanitize = true,
+            "list-types" => self.list_types = true,
+            "type" => {
+                self.content_type =
+                    Some(ContentType::from_name(value).ok_or_else(|| invalid("--type", value))?);
+            }
+            "detect" => match value {
+                "true" => self.detect = Some(true),
+                "false" => self.detect = Some(false),
+                _ => return Err(invalid("--detect", value)),
+            },
+            "mode" => {
+                self.mode = Some(parse_mode(value).ok_or_else(|| invalid("--mode", value))?);
+            }
+            "eol" => self.eol = Some(Eol::parse(value).ok_or_else(|| invalid("--eol", value))?),
+            "bom" => self.bom = Some(Bom::parse(value).ok_or_else(|| invalid("--bom", value))?),
+            "encoding" => {
+                self.encoding =
+                    Some(Encoding::parse(value).ok_or_else(|| invalid("--encoding", value))?);
+            }
+            "compress" => {
+                self.compress =
+                    Some(Compression::parse(value).ok_or_else(|| invalid("--compress", value))?);
+            }
+            "recipient" => self.recipients.push(value.to_string()),
+            "identity" => self.identity = Some(value.to_string()),
+            "interval" => {
+                let secs = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| *secs > 0.0 && secs.is_finite())
+                    .ok_or_else(|| invalid("--interval", value))?;
+                self.interval = Some(Duration::from_secs_f64(secs));
+            }
+            "older-than" => *older_than = Some(value.to_string()),
+            "extract-code" => {
+                self.extract_code = Some(match value {
+                    "" => CodeBlocks::All,
+                    n => CodeBlocks::Only(
+                        n.parse()
+                            .ok()
+                            .filter(|n| *n > 0)
+                            .ok_or_else(|| invalid("--extract-code", n))?,
+                    ),
+                });
+            }
+            "validate" => {
+                self.validate = Some(match value {
+                    "" | "fail" => Validation::Fail,
+                    "warn" => Validation::Warn,
+                    other => return Err(invalid("--validate", other)),
+                });
+            }
+            _ => unreachable!("option '{name}' missing from apply"),
+        }
+        Ok(())
+    }
+
+    fn with_filename(mut self, mut positionals: Vec<String>) -> Result<Self, ArgsError> {
+        if positionals.len() > 1 {
+            return Err(ArgsError::TooManyArgs);
+        }
+        self.filename = positionals.pop();
+        Ok(self)
+    }
+
     pub fn filename(&self) -> &str {
         self.filename.as_deref().unwrap_or("clipboard")
     }
@@ -81,12 +555,111 @@ impl Args {
     }
 }
 
+/// Options are parsed wherever they appear, so this is where one given to a
+/// subcommand it doesn't apply to, or without the option it needs, is caught.
+fn check_options(seen: &[&OptionSpec], command: &str) -> Result<(), ArgsError> {
+    for spec in seen {
+        if !spec.commands.is_empty() && !spec.commands.contains(&command) {
+            return Err(ArgsError::NotForCommand(
+                format!("--{}", spec.long),
+                command.to_string(),
+            ));
+        }
+        if let Some(required) = spec.requires {
+            if !seen.iter().any(|s| s.long == required) {
+                return Err(ArgsError::Requires(
+                    format!("--{}", spec.long),
+                    format!("--{required}"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn no_args(rest: Vec<String>, command: Command) -> Result<Command, ArgsError> {
+    if rest.is_empty() {
+        Ok(command)
+    } else {
+        Err(ArgsError::TooManyArgs)
+    }
+}
+
+fn single_arg(rest: &[String]) -> Result<Option<&str>, ArgsError> {
+    match rest {
+        [] => Ok(None),
+        [arg] => Ok(Some(arg)),
+        _ => Err(ArgsError::TooManyArgs),
+    }
+}
+
+fn parse_history(args: &[String], older_than: Option<String>) -> Result<HistoryCommand, ArgsError> {
+    let missing = |name: &str| ArgsError::MissingArgument(name.to_string());
+    let command = match args.first().map(String::as_str) {
+        None | Some("list") => {
+            if args.len() > 1 {
+                return Err(ArgsError::TooManyArgs);
+            }
+            return Ok(HistoryCommand::List);
+        }
+        Some(command) => command,
+    };
+
+    let rest = &args[1..];
+    if rest.len() > 1 || (command == "prune" && !rest.is_empty()) {
+        return Err(ArgsError::TooManyArgs);
+    }
+    match command {
+        "search" => rest
+            .first()
+            .map(|term| HistoryCommand::Search(term.to_string()))
+            .ok_or_else(|| missing("<term>")),
+        "restore" => {
+            let id = rest.first().ok_or_else(|| missing("<id>"))?;
+            id.parse()
+                .map(HistoryCommand::Restore)
+                .map_err(|_| invalid("<id>", id))
+        }
+        "prune" => {
+            let value = older_than.ok_or_else(|| missing("--older-than <age>"))?;
+            parse_age(&value)
+                .map(HistoryCommand::Prune)
+                .ok_or_else(|| invalid("--older-than", &value))
+        }
+        _ => Err(invalid("history", command)),
+    }
+}
+
+fn invalid(flag: &str, value: &str) -> ArgsError {
+    ArgsError::InvalidValue(flag.to_string(), value.to_string())
+}
+
+pub fn possible_values(flag: &str) -> Vec<&'static str> {
+    match flag {
+        "--detect" => vec!["true", "false"],
+        "--type" => ContentType::ALL.iter().map(|t| t.extension()).collect(),
+        "--eol" => Eol::VALUES.to_vec(),
+        "--bom" => Bom::VALUES.to_vec(),
+        "--encoding" => Encoding::VALUES.to_vec(),
+        "--compress" => Compression::VALUES.to_vec(),
+        "history" => vec!["list", "search", "restore", "prune"],
+        "config" => vec!["path", "show"],
+        "completions" => vec!["bash", "zsh", "fish"],
+        _ => Vec::new(),
+    }
+}
+
 #[derive(Debug)]
 pub enum ArgsError {
     Help,
     Version,
     UnknownFlag(String),
     InvalidValue(String, String),
+    MissingArgument(String),
+    // option, subcommand
+    NotForCommand(String, String),
+    // option, the option it needs
+    Requires(String, String),
     TooManyArgs,
 }
 
@@ -99,24 +672,48 @@ impl ArgsError {
     }
 }
 
+/// Renders an option as it appears in help, e.g. `-a, --append` or
+/// `    --detect <bool>`.
+pub fn option_label(spec: &OptionSpec) -> String {
+    let short = match spec.short {
+        Some(ch) => format!("-{ch}, "),
+        None => "    ".to_string(),
+    };
+    match spec.value {
+        Some(value) if spec.optional => format!("{short}--{}[={value}]", spec.long),
+        Some(value) => format!("{short}--{} {value}", spec.long),
+        None => format!("{short}--{}", spec.long),
+    }
+}
+
 pub fn print_help() {
     print_version();
     println!("Write clipboard contents to file");
     println!();
     println!("Usage: c2f [OPTIONS] [filename]");
+    println!("       c2f <COMMAND> [OPTIONS]");
+    println!();
+    println!("Commands:");
+    for command in COMMANDS {
+        println!("  {:<13}{}", command.name, command.help);
+    }
     println!();
     println!("Arguments:");
     println!(
         "  [filename]  File to write to. Defaults to 'clipboard' with auto-detected extension"
     );
+    println!("              Command names need 'c2f save <name>' or 'c2f -- <name>'");
     println!();
     println!("Options:");
-    println!("  -a, --append          Append to file instead of overwriting");
-    println!("  -q, --quiet           Suppress all output");
-    println!("  -e, --append-ext      Add detected extension to specified filename");
-    println!("      --detect=<bool>   Override content detection (true/false)");
-    println!("  -h, --help            Print help");
-    println!("  -V, --version         Print version");
+    let width = OPTIONS
+        .iter()
+        .map(|s| option_label(s).len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    for spec in OPTIONS {
+        println!("  {:<width$}{}", option_label(spec), spec.help);
+    }
 }
 
 pub fn print_version() {
@@ -134,7 +731,26 @@ pub fn print_error(err: &ArgsError) {
         }
         ArgsError::InvalidValue(flag, value) => {
             eprintln!("error: invalid value '{value}' for '{flag}'");
-            eprintln!("  [possible values: true, false]");
+            let values = possible_values(flag);
+            if !values.is_empty() {
+                eprintln!("  [possible values: {}]", values.join(", "));
+            }
+            eprintln!();
+            eprintln!("For more information, try '--help'.");
+        }
+        ArgsError::MissingArgument(name) => {
+            eprintln!("error: the following required arguments were not provided:");
+            eprintln!("  {name}");
+            eprintln!();
+            eprintln!("For more information, try '--help'.");
+        }
+        ArgsError::NotForCommand(flag, command) => {
+            eprintln!("error: '{flag}' can't be used with '{command}'");
+            eprintln!();
+            eprintln!("For more information, try '--help'.");
+        }
+        ArgsError::Requires(flag, required) => {
+            eprintln!("error: '{flag}' can only be used with '{required}'");
             eprintln!();
             eprintln!("For more information, try '--help'.");
         }
@@ -2,66 +2,13 @@
 // Licensed under the MIT License
 
 use crate::args::*;
+use crate::compress::Compression;
+use crate::detect::ContentType;
+use crate::encoding::{Bom, Encoding, Eol};
+use std::time::Duration;
 
 fn parse_args(cmd: &str) -> Result<Args, ArgsError> {
-    let args: Vec<String> = cmd.split_whitespace().map(String::from).collect();
-
-    let mut parsed = Args {
-        filename: None,
-        append: false,
-        quiet: false,
-        detect: None,
-        append_ext: false,
-    };
-
-    let mut i = 1; // skip 1st arg (self)
-    while i < args.len() {
-        let arg = &args[i];
-        match arg.as_str() {
-            "-h" | "--help" => return Err(ArgsError::Help),
-            "-V" | "--version" => return Err(ArgsError::Version),
-            "-a" | "--append" => parsed.append = true,
-            "-q" | "--quiet" => parsed.quiet = true,
-            "-e" | "--append-ext" => parsed.append_ext = true,
-            arg if arg.starts_with("--detect=") => {
-                let value = arg.strip_prefix("--detect=").unwrap();
-                match value {
-                    "true" => parsed.detect = Some(true),
-                    "false" => parsed.detect = Some(false),
-                    _ => {
-                        return Err(ArgsError::InvalidValue(
-                            "--detect".to_string(),
-                            value.to_string(),
-                        ))
-                    }
-                }
-            }
-            arg if arg.starts_with("-") && !arg.starts_with("--") && arg.len() > 2 => {
-                // combined
-                for ch in arg[1..].chars() {
-                    match ch {
-                        'a' => parsed.append = true,
-                        'q' => parsed.quiet = true,
-                        'e' => parsed.append_ext = true,
-                        'h' => return Err(ArgsError::Help),
-                        'V' => return Err(ArgsError::Version),
-                        _ => return Err(ArgsError::UnknownFlag(format!("-{ch}"))),
-                    }
-                }
-            }
-            arg if arg.starts_with('-') => return Err(ArgsError::UnknownFlag(arg.to_string())),
-            _ => {
-                if parsed.filename.is_none() {
-                    parsed.filename = Some(arg.to_string());
-                } else {
-                    return Err(ArgsError::TooManyArgs);
-                }
-            }
-        }
-        i += 1;
-    }
-
-    Ok(parsed)
+    Args::parse_from(cmd.split_whitespace().map(String::from).collect())
 }
 
 #[test]
@@ -214,6 +161,143 @@ fn test_invalid_detect_value() {
     ));
 }
 
+#[test]
+fn test_redact_flag() {
+    let args = parse_args("c2f --redact secrets.env").unwrap();
+    assert!(args.redact);
+    assert_eq!(args.filename, Some("secrets.env".to_string()));
+
+    assert!(!parse_args("c2f file").unwrap().redact);
+}
+
+#[test]
+fn test_mode_flag() {
+    let args = parse_args("c2f --mode=600 secrets.env").unwrap();
+    assert_eq!(args.mode, Some(0o600));
+
+    assert!(matches!(
+        parse_args("c2f --mode=999"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--mode"
+    ));
+}
+
+#[test]
+fn test_compress_flag() {
+    let args = parse_args("c2f --compress=zstd export").unwrap();
+    assert_eq!(args.compress, Some(Compression::Zstd));
+    assert_eq!(parse_args("c2f file").unwrap().compress, None);
+
+    assert!(matches!(
+        parse_args("c2f --compress=rar"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--compress"
+    ));
+}
+
+#[test]
+fn test_encryption_flags() {
+    let args = parse_args("c2f --encrypt notes.md").unwrap();
+    assert!(args.encrypt);
+    assert!(args.recipients.is_empty());
+
+    let args = parse_args("c2f --recipient=age1abc --recipient=age1def").unwrap();
+    assert_eq!(args.recipients, vec!["age1abc", "age1def"]);
+
+    let args = parse_args("c2f --decrypt --identity=key.txt --stdout notes.md.age").unwrap();
+    assert!(args.decrypt);
+    assert!(args.stdout);
+    assert_eq!(args.identity, Some("key.txt".to_string()));
+    assert_eq!(args.filename, Some("notes.md.age".to_string()));
+}
+
+#[test]
+fn test_text_output_flags() {
+    let args = parse_args("c2f --eol=crlf --bom=add --encoding=utf-16le file").unwrap();
+    assert_eq!(args.eol, Some(Eol::Crlf));
+    assert_eq!(args.bom, Some(Bom::Add));
+    assert_eq!(args.encoding, Some(Encoding::Utf16Le));
+
+    let args = parse_args("c2f --eol=LF file").unwrap();
+    assert_eq!(args.eol, Some(Eol::Lf));
+    assert_eq!(args.bom, None);
+}
+
+#[test]
+fn test_invalid_text_output_values() {
+    assert!(matches!(
+        parse_args("c2f --eol=cr"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--eol"
+    ));
+    assert!(matches!(
+        parse_args("c2f --bom=maybe"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--bom"
+    ));
+    assert!(matches!(
+        parse_args("c2f --encoding=ebcdic"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--encoding"
+    ));
+}
+
+#[test]
+fn test_history_commands() {
+    let command = |cmd| match parse_args(cmd).unwrap().command {
+        Command::History(command) => command,
+        _ => panic!("expected history command"),
+    };
+
+    assert_eq!(command("c2f history"), HistoryCommand::List);
+    assert_eq!(command("c2f history list"), HistoryCommand::List);
+    assert_eq!(
+        command("c2f history search token"),
+        HistoryCommand::Search("token".to_string())
+    );
+    assert_eq!(
+        command("c2f history restore 12"),
+        HistoryCommand::Restore(12)
+    );
+    assert_eq!(
+        command("c2f history prune --older-than 30d"),
+        HistoryCommand::Prune(Duration::from_secs(30 * 86400))
+    );
+    assert_eq!(
+        command("c2f -q history prune --older-than=2h"),
+        HistoryCommand::Prune(Duration::from_secs(2 * 3600))
+    );
+
+    // a filename first means "history" is just another argument
+    assert!(matches!(
+        parse_args("c2f notes.txt history"),
+        Err(ArgsError::TooManyArgs)
+    ));
+}
+
+#[test]
+fn test_invalid_history_commands() {
+    assert!(matches!(
+        parse_args("c2f history restore"),
+        Err(ArgsError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        parse_args("c2f history restore abc"),
+        Err(ArgsError::InvalidValue(_, _))
+    ));
+    assert!(matches!(
+        parse_args("c2f history prune"),
+        Err(ArgsError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        parse_args("c2f history prune --older-than 5y"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--older-than"
+    ));
+    assert!(matches!(
+        parse_args("c2f history forget"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "history"
+    ));
+    assert!(matches!(
+        parse_args("c2f history search a b"),
+        Err(ArgsError::TooManyArgs)
+    ));
+}
+
 #[test]
 fn test_too_many_args() {
     assert!(matches!(
@@ -250,13 +334,7 @@ fn test_exit_codes() {
 
 #[test]
 fn test_filename_methods() {
-    let mut args = Args {
-        filename: None,
-        append: false,
-        quiet: false,
-        detect: None,
-        append_ext: false,
-    };
+    let mut args = Args::default();
 
     assert_eq!(args.filename(), "clipboard");
     assert!(!args.has_explicit_filename());
@@ -265,3 +343,252 @@ fn test_filename_methods() {
     assert_eq!(args.filename(), "test.txt");
     assert!(args.has_explicit_filename());
 }
+
+#[test]
+fn test_option_value_forms() {
+    let args = parse_args("c2f --detect false --eol crlf notes.txt").unwrap();
+    assert_eq!(args.detect, Some(false));
+    assert_eq!(args.eol, Some(Eol::Crlf));
+    assert_eq!(args.filename, Some("notes.txt".to_string()));
+
+    assert!(matches!(
+        parse_args("c2f --eol"),
+        Err(ArgsError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        parse_args("c2f --quiet=yes"),
+        Err(ArgsError::UnknownFlag(_))
+    ));
+}
+
+#[test]
+fn test_terminator() {
+    let args = parse_args("c2f -q -- -notes.txt").unwrap();
+    assert!(args.quiet);
+    assert_eq!(args.filename, Some("-notes.txt".to_string()));
+
+    // after `--` a command name is just a filename
+    let args = parse_args("c2f -- history").unwrap();
+    assert_eq!(args.command, Command::Save);
+    assert_eq!(args.filename, Some("history".to_string()));
+}
+
+#[test]
+fn test_subcommands() {
+    let command = |cmd| parse_args(cmd).unwrap().command;
+    assert_eq!(command("c2f"), Command::Save);
+    assert_eq!(command("c2f save notes.txt"), Command::Save);
+    assert_eq!(command("c2f paste"), Command::Paste);
+    assert_eq!(command("c2f detect"), Command::Detect(Vec::new()));
+    assert_eq!(
+        command("c2f detect a b -"),
+        Command::Detect(vec!["a".to_string(), "b".to_string(), "-".to_string()])
+    );
+    assert!(parse_args("c2f detect --json a").unwrap().json);
+    assert_eq!(command("c2f man"), Command::Man);
+    assert_eq!(command("c2f config"), Command::Config(ConfigCommand::Path));
+    assert_eq!(
+        command("c2f config show"),
+        Command::Config(ConfigCommand::Show)
+    );
+    assert_eq!(
+        command("c2f completions zsh"),
+        Command::Completions(Shell::Zsh)
+    );
+
+    let args = parse_args("c2f watch --interval 0.5 log.txt -a").unwrap();
+    assert_eq!(args.command, Command::Watch);
+    assert_eq!(args.interval, Some(Duration::from_millis(500)));
+    assert_eq!(args.filename, Some("log.txt".to_string()));
+    assert!(args.append);
+
+    let args = parse_args("c2f save notes.txt").unwrap();
+    assert_eq!(args.filename, Some("notes.txt".to_string()));
+}
+
+#[test]
+fn test_invalid_subcommands() {
+    assert!(matches!(
+        parse_args("c2f paste extra"),
+        Err(ArgsError::TooManyArgs)
+    ));
+    assert!(matches!(
+        parse_args("c2f completions"),
+        Err(ArgsError::MissingArgument(_))
+    ));
+    assert!(matches!(
+        parse_args("c2f completions powershell"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "completions"
+    ));
+    assert!(matches!(
+        parse_args("c2f watch --interval 0"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--interval"
+    ));
+    assert!(matches!(
+        parse_args("c2f --older-than 30d"),
+        Err(ArgsError::UnknownFlag(_))
+    ));
+}
+
+#[test]
+fn test_options_checked_against_subcommand() {
+    let not_for = |cmd| match parse_args(cmd) {
+        Err(ArgsError::NotForCommand(flag, command)) => Some((flag, command)),
+        _ => None,
+    };
+    assert_eq!(
+        not_for("c2f paste --append"),
+        Some(("--append".to_string(), "paste".to_string()))
+    );
+    assert_eq!(
+        not_for("c2f detect --encrypt a.txt"),
+        Some(("--encrypt".to_string(), "detect".to_string()))
+    );
+    assert_eq!(
+        not_for("c2f --json notes.txt"),
+        Some(("--json".to_string(), "save".to_string()))
+    );
+    assert_eq!(
+        not_for("c2f -qa config"),
+        Some(("--append".to_string(), "config".to_string()))
+    );
+    assert_eq!(
+        not_for("c2f watch --split"),
+        Some(("--split".to_string(), "watch".to_string()))
+    );
+
+    // options shared by every command, or meant for the one given
+    assert!(parse_args("c2f -q paste").is_ok());
+    assert!(parse_args("c2f detect --json a.txt").is_ok());
+    assert!(parse_args("c2f watch -a --interval 2 log.txt").is_ok());
+    assert!(parse_args("c2f save --encrypt notes.txt").is_ok());
+    // after `--` it's a filename, so saving options apply
+    assert!(parse_args("c2f -a -- paste").is_ok());
+}
+
+#[test]
+fn test_options_requiring_another() {
+    let requires = |cmd| match parse_args(cmd) {
+        Err(ArgsError::Requires(flag, required)) => Some((flag, required)),
+        _ => None,
+    };
+    assert_eq!(
+        requires("c2f --stdout notes.txt.age"),
+        Some(("--stdout".to_string(), "--decrypt".to_string()))
+    );
+    assert_eq!(
+        requires("c2f --force out"),
+        Some(("--force".to_string(), "--split".to_string()))
+    );
+    assert!(parse_args("c2f --decrypt --stdout notes.txt.age").is_ok());
+    assert!(parse_args("c2f --stdout --identity key.txt --decrypt a.age").is_ok());
+}
+
+#[test]
+fn test_type_flag() {
+    let content_type = |cmd| parse_args(cmd).unwrap().content_type;
+    assert_eq!(content_type("c2f"), None);
+    assert_eq!(content_type("c2f --type yaml"), Some(ContentType::Yaml));
+    assert_eq!(content_type("c2f --type=YAML"), Some(ContentType::Yaml));
+    assert_eq!(content_type("c2f -t py"), Some(ContentType::Python));
+    assert_eq!(content_type("c2f -tpy"), Some(ContentType::Python));
+
+    let args = parse_args("c2f -qet rs notes").unwrap();
+    assert!(args.quiet && args.append_ext);
+    assert_eq!(args.content_type, Some(ContentType::Rust));
+    assert_eq!(args.filename, Some("notes".to_string()));
+
+    assert!(matches!(
+        parse_args("c2f -t cobol"),
+        Err(ArgsError::InvalidValue(flag, _)) if flag == "--type"
+    ));
+    assert!(matches!(
+        parse_args("c2f -qt"),
+        Err(ArgsError::MissingArgument(_))
+    ));
+}
+
+#[test]
+fn test_list_types_flag() {
+    assert!(parse_args("c2f --list-types").unwrap().list_types);
+    assert!(!parse_args("c2f").unwrap().list_types);
+}
+
+#[test]
+fn test_apply_flag() {
+    assert!(parse_args("c2f --apply").unwrap().apply);
+    assert!(!parse_args("c2f").unwrap().apply);
+}
+
+#[test]
+fn test_extract_code_flag() {
+    assert_eq!(parse_args("c2f").unwrap().extract_code, None);
+
+    // the block number is only taken inline, so a filename can follow
+    let args = parse_args("c2f --extract-code snippet").unwrap();
+    assert_eq!(args.extract_code, Some(CodeBlocks::All));
+    assert_eq!(args.filename, Some("snippet".to_string()));
+
+    let args = parse_args("c2f --extract-code=2").unwrap();
+    assert_eq!(args.extract_code, Some(CodeBlocks::Only(2)));
+    assert_eq!(args.filename, None);
+
+    for value in ["0", "-1", "two"] {
+        assert!(matches!(
+            parse_args(&format!("c2f --extract-code={value}")),
+            Err(ArgsError::InvalidValue(_, _))
+        ));
+    }
+
+    let spec = OPTIONS.iter().find(|s| s.long == "extract-code").unwrap();
+    assert_eq!(option_label(spec), "    --extract-code[=<n>]");
+    assert_eq!(spec.separate_value(), None);
+}
+
+#[test]
+fn test_split_flags() {
+    let args = parse_args("c2f --split --force out").unwrap();
+    assert!(args.split && args.force);
+    assert_eq!(args.filename, Some("out".to_string()));
+
+    let args = parse_args("c2f").unwrap();
+    assert!(!args.split && !args.force);
+}
+
+#[test]
+fn test_convert_flag() {
+    let args = parse_args("c2f --convert config.yaml").unwrap();
+    assert!(args.convert);
+    assert_eq!(args.filename, Some("config.yaml".to_string()));
+
+    assert!(!parse_args("c2f config.yaml").unwrap().convert);
+}
+
+#[test]
+fn test_validate_flag() {
+    assert_eq!(parse_args("c2f").unwrap().validate, None);
+
+    let args = parse_args("c2f --validate config.toml").unwrap();
+    assert_eq!(args.validate, Some(Validation::Fail));
+    assert_eq!(args.filename, Some("config.toml".to_string()));
+
+    let args = parse_args("c2f --validate=warn").unwrap();
+    assert_eq!(args.validate, Some(Validation::Warn));
+
+    let args = parse_args("c2f --validate=fail").unwrap();
+    assert_eq!(args.validate, Some(Validation::Fail));
+
+    assert!(matches!(
+        parse_args("c2f --validate=yes"),
+        Err(ArgsError::InvalidValue(_, _))
+    ));
+
+    let spec = OPTIONS.iter().find(|s| s.long == "validate").unwrap();
+    assert_eq!(option_label(spec), "    --validate[=warn]");
+}
+
+#[test]
+fn test_sanitize_flag() {
+    assert!(parse_args("c2f --sanitize main.py").unwrap().sanitize);
+    assert!(!parse_args("c2f main.py").unwrap().sanitize);
+}
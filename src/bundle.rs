@@ -0,0 +1,184 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Multi-file bundles for --split: several files pasted together, each
+// introduced by a header naming its path. Recognised headers are
+//
+//   ==> src/a.txt <==          head/tail over several files
+//   // file: src/main.rs       a comment marker in any common comment style
+//   **src/main.rs**            a Markdown line right before a fenced block
+
+use crate::fences;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, PartialEq)]
+pub struct BundleFile {
+    pub path: String,
+    pub content: String,
+}
+
+const COMMENT_OPENERS: &[&str] = &["<!--", "/*", "//", "--", "#", ";", "%"];
+const COMMENT_CLOSERS: &[&str] = &["-->", "*/"];
+const MARKER_WORDS: &[&str] = &["file", "filename", "path"];
+
+/// Splits `text` at header lines. Text before the first header is ignored.
+/// Falls back to Markdown-style bundles when there are no header lines.
+pub fn split(text: &str) -> Vec<BundleFile> {
+    let mut files: Vec<BundleFile> = Vec::new();
+    for line in text.lines() {
+        if let Some(path) = header_path(line) {
+            files.push(BundleFile {
+                path: path.to_string(),
+                content: String::new(),
+            });
+        } else if let Some(file) = files.last_mut() {
+            file.content.push_str(line);
+            file.content.push('\n');
+        }
+    }
+    if files.is_empty() {
+        return markdown_files(text);
+    }
+    for file in &mut files {
+        file.content = tidy(&file.content);
+    }
+    files
+}
+
+/// The path named by a `==> path <==` or comment marker line.
+fn header_path(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if let Some(path) = line
+        .strip_prefix("==> ")
+        .and_then(|rest| rest.strip_suffix(" <=="))
+    {
+        return Some(path.trim()).filter(|p| !p.is_empty());
+    }
+
+    let opener = COMMENT_OPENERS.iter().find(|o| line.starts_with(*o))?;
+    let mut rest = line[opener.len()..].trim_start_matches(*opener);
+    if let Some(closer) = COMMENT_CLOSERS.iter().find(|c| rest.ends_with(*c)) {
+        rest = &rest[..rest.len() - closer.len()];
+    }
+    let (word, path) = rest.split_once(':')?;
+    let word = word.trim();
+    if !MARKER_WORDS.iter().any(|w| w.eq_ignore_ascii_case(word)) {
+        return None;
+    }
+    let path = path.trim();
+    Some(path).filter(|p| !p.is_empty() && !p.contains(char::is_whitespace))
+}
+
+/// Fenced blocks whose closest non-blank line before the fence is a path,
+/// like `### src/main.rs`, `**Cargo.toml**` or `` `app.py`: ``.
+fn markdown_files(text: &str) -> Vec<BundleFile> {
+    let lines: Vec<&str> = text.lines().collect();
+    fences::code_blocks(text)
+        .into_iter()
+        .filter_map(|block| {
+            let before = lines[..block.line]
+                .iter()
+                .rev()
+                .find(|l| !l.trim().is_empty())?;
+            let path = markdown_path(before)?;
+            Some(BundleFile {
+                path: path.to_string(),
+                content: block.body,
+            })
+        })
+        .collect()
+}
+
+fn markdown_path(line: &str) -> Option<&str> {
+    let mut path = line
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_end_matches(':');
+    for marker in ["**", "__", "`"] {
+        path = path.trim_matches(|c| marker.contains(c));
+    }
+    // "File: x", inside the decoration or around it
+    let mut marked = false;
+    if let Some((word, rest)) = path.split_once(':') {
+        if MARKER_WORDS
+            .iter()
+            .any(|w| w.eq_ignore_ascii_case(word.trim()))
+        {
+            path = rest.trim_matches(|c: char| c == '*' || c == '`' || c.is_whitespace());
+            marked = true;
+        }
+    }
+    // headings like "## Usage" aren't paths, "## Makefile" needs a marker
+    let path_like = path.contains('.') || path.contains('/');
+    Some(path)
+        .filter(|p| !p.is_empty() && !p.contains(char::is_whitespace) && (path_like || marked))
+}
+
+/// Drops the blank lines around a file, and the fence when a whole file
+/// is one fenced block.
+fn tidy(content: &str) -> String {
+    let trimmed = content.trim_matches('\n');
+    let is_fenced = ["```", "~~~"]
+        .iter()
+        .any(|fence| trimmed.starts_with(fence) && trimmed.ends_with(fence));
+    if is_fenced {
+        let blocks = fences::code_blocks(trimmed);
+        if let [block] = blocks.as_slice() {
+            return block.body.clone();
+        }
+    }
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{trimmed}\n")
+    }
+}
+
+/// Checks a path taken from a header: it has to stay inside the output
+/// directory, so absolute paths and `..` are refused.
+pub fn relative_path(path: &str) -> Result<PathBuf, String> {
+    let relative = PathBuf::from(path.replace('\\', "/"));
+    let mut clean = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(format!(
+                    "Refusing to write outside the output directory: '{path}'"
+                ))
+            }
+        }
+    }
+    if clean.as_os_str().is_empty() {
+        return Err(format!("Invalid path in bundle header: '{path}'"));
+    }
+    Ok(clean)
+}
+
+/// Makes sure `target` doesn't leave `base` through a symlink, by resolving
+/// the deepest part of it that already exists.
+pub fn check_inside(base: &Path, target: &Path) -> Result<(), String> {
+    let base = fs::canonicalize(base)
+        .map_err(|e| format!("Error reading directory '{}': {e}", base.display()))?;
+    let existing = target
+        .ancestors()
+        .find(|p| fs::symlink_metadata(p).is_ok())
+        .unwrap_or(target);
+    let resolved = fs::canonicalize(existing)
+        .map_err(|e| format!("Error reading '{}': {e}", existing.display()))?;
+    if resolved.starts_with(&base) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Refusing to write outside the output directory: '{}'",
+            target.display()
+        ))
+    }
+}
+
+#[cfg(test)]
+#[path = "bundle_tests.rs"]
+mod tests;
@@ -0,0 +1,92 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::bundle::*;
+use std::fs;
+use std::path::PathBuf;
+
+fn paths(files: &[BundleFile]) -> Vec<&str> {
+    files.iter().map(|f| f.path.as_str()).collect()
+}
+
+#[test]
+fn test_split_head_output() {
+    let text = "==> a.txt <==\nfirst\n\n==> dir/b.txt <==\nsecond\nmore\n";
+    let files = split(text);
+    assert_eq!(paths(&files), ["a.txt", "dir/b.txt"]);
+    assert_eq!(files[0].content, "first\n");
+    assert_eq!(files[1].content, "second\nmore\n");
+}
+
+#[test]
+fn test_split_comment_markers() {
+    let text = "Here are the files:\n\
+        // file: src/main.rs\nfn main() {}\n\
+        # File: setup.py\nimport os\n\
+        <!-- file: index.html -->\n<p>hi</p>\n\
+        -- filename: schema.sql\nSELECT 1;\n";
+    let files = split(text);
+    assert_eq!(
+        paths(&files),
+        ["src/main.rs", "setup.py", "index.html", "schema.sql"]
+    );
+    // the preamble is dropped
+    assert_eq!(files[0].content, "fn main() {}\n");
+    assert_eq!(files[2].content, "<p>hi</p>\n");
+
+    // ordinary comments aren't headers
+    assert!(split("// see file: the docs\n# note: nothing\n---\n").is_empty());
+}
+
+#[test]
+fn test_split_unwraps_fenced_files() {
+    let text = "// file: app.py\n```python\nprint(1)\n```\n\n// file: notes.md\n# Title\n\n```\ncode\n```\n";
+    let files = split(text);
+    assert_eq!(files[0].content, "print(1)\n");
+    // a file that merely contains a fence keeps it
+    assert_eq!(files[1].content, "# Title\n\n```\ncode\n```\n");
+}
+
+#[test]
+fn test_split_markdown() {
+    let text = "Create these:\n\n### src/lib.rs\n```rust\npub fn f() {}\n```\n\n\
+        **Cargo.toml**\n\n```toml\n[package]\n```\n\n\
+        `app.py`:\n```python\nx = 1\n```\n\n\
+        **File:** Makefile\n```make\nall:\n```\n\n\
+        ## Usage\n```bash\ncargo run\n```\n";
+    let files = split(text);
+    assert_eq!(
+        paths(&files),
+        ["src/lib.rs", "Cargo.toml", "app.py", "Makefile"]
+    );
+    assert_eq!(files[1].content, "[package]\n");
+}
+
+#[test]
+fn test_relative_path() {
+    assert_eq!(
+        relative_path("./src/main.rs").unwrap(),
+        PathBuf::from("src/main.rs")
+    );
+    assert_eq!(relative_path("a\\b.txt").unwrap(), PathBuf::from("a/b.txt"));
+    for path in ["../escape.txt", "src/../../x", "/etc/passwd", ".", ""] {
+        assert!(relative_path(path).is_err(), "{path}");
+    }
+}
+
+#[test]
+fn test_check_inside() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("out");
+    fs::create_dir(&base).unwrap();
+    assert!(check_inside(&base, &base.join("new/dir/file.txt")).is_ok());
+
+    #[cfg(unix)]
+    {
+        let outside = dir.path().join("outside");
+        fs::create_dir(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, base.join("link")).unwrap();
+        assert!(check_inside(&base, &base.join("link/file.txt")).is_err());
+        assert!(check_inside(&base, &base.join("link")).is_err());
+    }
+}
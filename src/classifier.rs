@@ -0,0 +1,76 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Naive Bayes classifier over token unigrams and bigrams. build.rs trains it
+// from data/classifier and compiles the model in as static tables.
+
+use crate::detect::ContentType;
+use crate::tokens;
+use std::cmp::Ordering;
+
+include!(concat!(env!("OUT_DIR"), "/classifier_model.rs"));
+
+/// Log-prior for every type a rule-based detector matched.
+const RULE_PRIOR: f32 = 2.0;
+/// Extra log-prior for the first match, which is what the rules alone would
+/// answer. Together they decide short snippets, where the token evidence is
+/// thin, and get outweighed when a longer text clearly reads like something
+/// else.
+const FIRST_MATCH_PRIOR: f32 = 6.0;
+
+pub struct Prediction {
+    pub content_type: ContentType,
+    /// Average log-probability lead per feature over the runner-up. Around
+    /// 0.1 is a clear call; near 0 is a coin toss.
+    pub margin: f32,
+}
+
+/// Picks the most likely type for `text`, giving the types in `matched`
+/// (the ones the rule-based detectors claim, in priority order) a head
+/// start. Returns `None`
+/// when there is nothing to go on.
+pub fn classify(text: &str, matched: &[ContentType]) -> Option<Prediction> {
+    let features = tokens::features(text);
+    if features.is_empty() {
+        return None;
+    }
+
+    let n = features.len() as f32;
+    let mut scores: Vec<f32> = UNSEEN.iter().map(|unseen| unseen * n).collect();
+    for feature in &features {
+        if let Ok(i) = VOCABULARY.binary_search_by(|(f, _)| (*f).cmp(feature.as_str())) {
+            for &(class, delta) in VOCABULARY[i].1 {
+                scores[class as usize] += delta;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(ContentType, f32)> = LABELS
+        .iter()
+        .zip(scores)
+        .filter_map(|(label, score)| {
+            let content_type = ContentType::from_name(label)?;
+            let prior = match matched.iter().position(|t| *t == content_type) {
+                Some(0) => RULE_PRIOR + FIRST_MATCH_PRIOR,
+                Some(_) => RULE_PRIOR,
+                None => 0.0,
+            };
+            Some((content_type, score + prior))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+
+    let mut ranked = ranked.into_iter();
+    let (content_type, best) = ranked.next()?;
+    let margin = ranked
+        .next()
+        .map_or(f32::INFINITY, |(_, second)| (best - second) / n);
+    Some(Prediction {
+        content_type,
+        margin,
+    })
+}
+
+#[cfg(test)]
+#[path = "classifier_tests.rs"]
+mod tests;
@@ -0,0 +1,56 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::classifier::*;
+use crate::detect::ContentType;
+use crate::tokens;
+
+#[test]
+fn test_model_labels_are_content_types() {
+    for label in LABELS {
+        assert!(ContentType::from_name(label).is_some(), "{label}");
+    }
+    assert_eq!(LABELS.len(), UNSEEN.len());
+}
+
+#[test]
+fn test_classify() {
+    let predict = |text| classify(text, &[]).unwrap().content_type;
+    assert_eq!(
+        predict("fn main() {\n    let mut v = Vec::new();\n    v.push(1);\n}"),
+        ContentType::Rust
+    );
+    assert_eq!(
+        predict("def greet(name):\n    return f\"hi {name}\"\n"),
+        ContentType::Python
+    );
+    assert!(classify("", &[]).is_none());
+}
+
+#[test]
+fn test_rule_matches_get_a_head_start() {
+    // ambiguous on its own, so the matched rule decides
+    let text = "x = 1";
+    let prediction = classify(text, &[ContentType::Toml]).unwrap();
+    assert_eq!(prediction.content_type, ContentType::Toml);
+
+    // but not against text that clearly reads like something else
+    let text = "fn main() {\n    let mut v = Vec::new();\n    v.push(1);\n}";
+    let prediction = classify(text, &[ContentType::Toml]).unwrap();
+    assert_eq!(prediction.content_type, ContentType::Rust);
+}
+
+#[test]
+fn test_classify_caps_its_input() {
+    // only the first tokens::MAX_BYTES count, so huge inputs stay cheap;
+    // timing is in bench_detect_throughput
+    let head = "def greet(name):\n    return f\"hi {name}\"\n".repeat(tokens::MAX_BYTES / 16);
+    let text = format!(
+        "{head}{}",
+        "fn main() { let v = Vec::new(); }\n".repeat(10_000)
+    );
+    assert_eq!(
+        classify(&text, &[]).unwrap().content_type,
+        ContentType::Python
+    );
+}
@@ -0,0 +1,232 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::args::{option_label, possible_values, Shell, COMMANDS, OPTIONS};
+
+pub fn generate(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+fn command_names() -> Vec<&'static str> {
+    COMMANDS.iter().map(|c| c.name).collect()
+}
+
+fn bash() -> String {
+    let mut words: Vec<String> = command_names().iter().map(|c| c.to_string()).collect();
+    for spec in OPTIONS {
+        words.push(format!("--{}", spec.long));
+        if let Some(short) = spec.short {
+            words.push(format!("-{short}"));
+        }
+    }
+
+    let mut value_cases = String::new();
+    for spec in OPTIONS.iter().filter(|s| s.separate_value().is_some()) {
+        let flag = format!("--{}", spec.long);
+        let values = possible_values(&flag);
+        let action = if values.is_empty() {
+            "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string()
+        } else {
+            format!(
+                "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                values.join(" ")
+            )
+        };
+        value_cases.push_str(&format!(
+            "        {flag})\n            {action}\n            return ;;\n"
+        ));
+    }
+
+    let mut command_cases = String::new();
+    for command in ["history", "config", "completions"] {
+        command_cases.push_str(&format!(
+            "        {command})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return ;;\n",
+            possible_values(command).join(" ")
+        ));
+    }
+
+    format!(
+        r#"# bash completion for c2f
+_c2f() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+{value_cases}{command_cases}    esac
+
+    if [[ "$cur" == -* || $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{words}" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -f -- "$cur"))
+    fi
+}}
+complete -F _c2f c2f
+"#,
+        words = words.join(" ")
+    )
+}
+
+fn zsh() -> String {
+    let mut specs = Vec::new();
+    for spec in OPTIONS {
+        let help = spec
+            .help
+            .replace('[', "\\[")
+            .replace(']', "\\]")
+            .replace('\'', "");
+        let values = possible_values(&format!("--{}", spec.long));
+        let action = match spec.separate_value() {
+            Some(value) if values.is_empty() => format!(":{value}:_files"),
+            Some(value) => format!(":{value}:({})", values.join(" ")),
+            None => String::new(),
+        };
+        let long = format!("--{}", spec.long);
+        let long = if spec.separate_value().is_some() {
+            format!("{long}=")
+        } else {
+            long
+        };
+        match spec.short {
+            Some(short) => specs.push(format!(
+                "'(-{short} --{name})'{{-{short},{long}}}'[{help}]{action}'",
+                name = spec.long
+            )),
+            None => specs.push(format!("'{long}[{help}]{action}'")),
+        }
+    }
+
+    let commands: Vec<String> = COMMANDS
+        .iter()
+        .map(|c| format!("'{}:{}'", c.name, c.help.replace('\'', "")))
+        .collect();
+
+    format!(
+        r#"#compdef c2f
+
+_c2f() {{
+    local -a commands
+    commands=(
+        {commands}
+    )
+
+    _arguments -s \
+        {specs} \
+        '1: :->first' \
+        '*:: :->rest'
+
+    case $state in
+        first)
+            _describe 'command' commands
+            _files
+            ;;
+        rest)
+            case $words[1] in
+                history) _values 'history command' {history} ;;
+                config) _values 'config command' {config} ;;
+                completions) _values 'shell' {completions} ;;
+                *) _files ;;
+            esac
+            ;;
+    esac
+}}
+
+_c2f "$@"
+"#,
+        commands = commands.join("\n        "),
+        specs = specs.join(" \\\n        "),
+        history = possible_values("history").join(" "),
+        config = possible_values("config").join(" "),
+        completions = possible_values("completions").join(" "),
+    )
+}
+
+fn fish() -> String {
+    let mut out = String::from("# fish completion for c2f\ncomplete -c c2f -f\n");
+    let no_command = format!(
+        "not __fish_seen_subcommand_from {}",
+        command_names().join(" ")
+    );
+
+    for command in COMMANDS {
+        out.push_str(&format!(
+            "complete -c c2f -n '{no_command}' -a {} -d '{}'\n",
+            command.name,
+            command.help.replace('\'', "\\'")
+        ));
+    }
+    for command in ["history", "config", "completions"] {
+        out.push_str(&format!(
+            "complete -c c2f -n '__fish_seen_subcommand_from {command}' -a '{}'\n",
+            possible_values(command).join(" ")
+        ));
+    }
+
+    for spec in OPTIONS {
+        let mut line = format!("complete -c c2f -l {}", spec.long);
+        if let Some(short) = spec.short {
+            line.push_str(&format!(" -s {short}"));
+        }
+        if spec.separate_value().is_some() {
+            let values = possible_values(&format!("--{}", spec.long));
+            if values.is_empty() {
+                line.push_str(" -r -F");
+            } else {
+                line.push_str(&format!(" -x -a '{}'", values.join(" ")));
+            }
+        }
+        line.push_str(&format!(" -d '{}'\n", spec.help.replace('\'', "\\'")));
+        out.push_str(&line);
+    }
+
+    // filenames for save and watch
+    out.push_str(&format!(
+        "complete -c c2f -n '{no_command}; or __fish_seen_subcommand_from save watch' -F\n"
+    ));
+    out
+}
+
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('-', "\\-")
+}
+
+pub fn man() -> String {
+    let mut out = format!(
+        ".TH C2F 1 \"\" \"c2f {}\" \"User Commands\"\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    out.push_str(".SH NAME\nc2f \\- write clipboard contents to a file\n");
+    out.push_str(".SH SYNOPSIS\n.B c2f\n[\\fIOPTIONS\\fR] [\\fIfilename\\fR]\n.br\n.B c2f\n\\fICOMMAND\\fR [\\fIOPTIONS\\fR]\n");
+    out.push_str(".SH DESCRIPTION\nSaves or appends the clipboard contents to a file, detecting the content type and adding an appropriate extension when no filename is given.\n");
+
+    out.push_str(".SH COMMANDS\n");
+    for command in COMMANDS {
+        out.push_str(&format!(
+            ".TP\n\\fB{}\\fR {}\n{}\n",
+            command.name,
+            roff_escape(command.args),
+            roff_escape(command.help)
+        ));
+    }
+
+    out.push_str(".SH OPTIONS\n");
+    for spec in OPTIONS {
+        out.push_str(&format!(
+            ".TP\n\\fB{}\\fR\n{}\n",
+            roff_escape(option_label(spec).trim()),
+            roff_escape(spec.help)
+        ));
+    }
+
+    out.push_str(".SH FILES\n.TP\n\\fI~/.config/c2f/config.toml\\fR\nConfiguration file.\n.TP\n\\fI~/.local/share/c2f/\\fR\nClipboard history, when enabled.\n");
+    out.push_str(".SH SEE ALSO\nhttps://github.com/balintb/c2f\n");
+    out
+}
+
+#[cfg(test)]
+#[path = "completions_tests.rs"]
+mod tests;
@@ -0,0 +1,45 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::args::{Shell, COMMANDS, OPTIONS};
+use crate::completions::*;
+
+#[test]
+fn test_completions_cover_all_options_and_commands() {
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+        let script = generate(shell);
+        for spec in OPTIONS {
+            assert!(
+                script.contains(spec.long),
+                "{shell:?} completion missing --{}",
+                spec.long
+            );
+        }
+        for command in COMMANDS {
+            assert!(script.contains(command.name));
+        }
+    }
+}
+
+#[test]
+fn test_completions_offer_possible_values() {
+    assert!(generate(Shell::Bash).contains("lf crlf native keep"));
+    assert!(generate(Shell::Zsh).contains("(gzip zstd)"));
+    assert!(generate(Shell::Fish).contains("-l eol -x -a 'lf crlf native keep'"));
+}
+
+#[test]
+fn test_shell_headers() {
+    assert!(generate(Shell::Bash).contains("complete -F _c2f c2f"));
+    assert!(generate(Shell::Zsh).starts_with("#compdef c2f"));
+    assert!(generate(Shell::Fish).starts_with("# fish completion"));
+}
+
+#[test]
+fn test_man_page() {
+    let page = man();
+    assert!(page.starts_with(".TH C2F 1"));
+    assert!(page.contains(".SH OPTIONS"));
+    assert!(page.contains("\\fB\\-a, \\-\\-append\\fR"));
+    assert!(page.contains("\\fBhistory\\fR"));
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::output::Finish;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{self, Read};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub const VALUES: &'static [&'static str] = &["gzip", "zstd"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "gzip" | "gz" => Some(Compression::Gzip),
+            "zstd" | "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+        }
+    }
+
+    /// Splits a compression suffix off a filename, e.g. `logs.txt.gz` into
+    /// `logs.txt` and gzip.
+    pub fn split_filename(filename: &str) -> Option<(&str, Self)> {
+        let (stem, extension) = filename.rsplit_once('.')?;
+        if stem.is_empty() || stem.ends_with(['/', '\\']) {
+            return None;
+        }
+        let compression = match extension.to_lowercase().as_str() {
+            "gz" | "gzip" => Compression::Gzip,
+            "zst" | "zstd" => Compression::Zstd,
+            _ => return None,
+        };
+        Some((stem, compression))
+    }
+
+    /// Recognises a gzip or zstd stream by its magic bytes.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Compression::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Compresses everything written into a single self-contained gzip member
+/// or zstd frame, so appending to an existing file yields a valid
+/// multi-member stream.
+pub fn encoder<'a>(
+    writer: Box<dyn Finish + 'a>,
+    compression: Compression,
+) -> Result<Box<dyn Finish + 'a>, String> {
+    Ok(match compression {
+        Compression::Gzip => Box::new(GzEncoder::new(writer, flate2::Compression::default())),
+        Compression::Zstd => {
+            Box::new(zstd::Encoder::new(writer, 0).map_err(|e| format!("Failed to compress: {e}"))?)
+        }
+    })
+}
+
+/// Decompresses a whole file, including gzip members and zstd frames added
+/// by later appends.
+pub fn decompress(bytes: &[u8], compression: Compression) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    match compression {
+        Compression::Gzip => MultiGzDecoder::new(bytes).read_to_end(&mut out).map(drop),
+        Compression::Zstd => zstd::stream::copy_decode(bytes, &mut out),
+    }
+    .map_err(|e| format!("Failed to decompress: {e}"))?;
+    Ok(out)
+}
+
+impl Finish for GzEncoder<Box<dyn Finish + '_>> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        GzEncoder::finish(*self)?.finish()
+    }
+}
+
+impl Finish for zstd::Encoder<'static, Box<dyn Finish + '_>> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        zstd::Encoder::finish(*self)?.finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "compress_tests.rs"]
+mod tests;
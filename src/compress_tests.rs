@@ -0,0 +1,92 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::compress::*;
+use std::io::{self, Read, Write};
+
+fn compress(bytes: &[u8], compression: Compression) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut writer = encoder(Box::new(io::BufWriter::new(&mut out)), compression)?;
+    writer
+        .write_all(bytes)
+        .and_then(|_| writer.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(Compression::parse("gzip"), Some(Compression::Gzip));
+    assert_eq!(Compression::parse("ZSTD"), Some(Compression::Zstd));
+    assert_eq!(Compression::parse("zip"), None);
+}
+
+#[test]
+fn test_split_filename() {
+    assert_eq!(
+        Compression::split_filename("logs.txt.gz"),
+        Some(("logs.txt", Compression::Gzip))
+    );
+    assert_eq!(
+        Compression::split_filename("export.zst"),
+        Some(("export", Compression::Zstd))
+    );
+    assert_eq!(Compression::split_filename("notes.txt"), None);
+    assert_eq!(Compression::split_filename("noext"), None);
+    assert_eq!(Compression::split_filename(".gz"), None);
+    assert_eq!(Compression::split_filename("dir/.gz"), None);
+}
+
+#[test]
+fn test_gzip_round_trip() {
+    let compressed = compress(b"a,b\n1,2\n", Compression::Gzip).unwrap();
+    assert!(compressed.starts_with(&[0x1f, 0x8b]));
+
+    let mut out = String::new();
+    flate2::read::GzDecoder::new(&compressed[..])
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "a,b\n1,2\n");
+}
+
+#[test]
+fn test_appended_gzip_members() {
+    let mut file = compress(b"first\n", Compression::Gzip).unwrap();
+    file.extend(compress(b"second\n", Compression::Gzip).unwrap());
+
+    let mut out = String::new();
+    flate2::read::MultiGzDecoder::new(&file[..])
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "first\nsecond\n");
+}
+
+#[test]
+fn test_appended_zstd_frames() {
+    let mut file = compress(b"first\n", Compression::Zstd).unwrap();
+    file.extend(compress(b"second\n", Compression::Zstd).unwrap());
+
+    let out = zstd::decode_all(&file[..]).unwrap();
+    assert_eq!(out, b"first\nsecond\n");
+}
+
+#[test]
+fn test_sniff() {
+    let gzip = compress(b"x", Compression::Gzip).unwrap();
+    let zstd = compress(b"x", Compression::Zstd).unwrap();
+    assert_eq!(Compression::sniff(&gzip), Some(Compression::Gzip));
+    assert_eq!(Compression::sniff(&zstd), Some(Compression::Zstd));
+    assert_eq!(Compression::sniff(b"plain text"), None);
+}
+
+#[test]
+fn test_decompress() {
+    for compression in [Compression::Gzip, Compression::Zstd] {
+        let mut file = compress(b"first\n", compression).unwrap();
+        file.extend(compress(b"second\n", compression).unwrap());
+        assert_eq!(decompress(&file, compression).unwrap(), b"first\nsecond\n");
+    }
+    assert!(decompress(b"plain", Compression::Gzip)
+        .unwrap_err()
+        .starts_with("Failed to decompress"));
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Data format conversion for --convert: JSON, YAML and TOML are read into a
+// JSON value and written from it, so JSON is the hub every conversion goes
+// through. Key order is kept. Data the target can't hold is an error rather
+// than something dropped or made up.
+
+use crate::detect::ContentType;
+use crate::yaml;
+use serde_json::{Map, Value};
+
+// how the toml crate hands datetimes to a deserializer
+const TOML_DATETIME: &str = "$__toml_private_datetime";
+
+/// The data format a content type is read and written as. Kubernetes
+/// manifests are YAML.
+pub fn data_format(content_type: &ContentType) -> Option<ContentType> {
+    match content_type {
+        ContentType::Json | ContentType::Yaml | ContentType::Toml => Some(content_type.clone()),
+        ContentType::Kubernetes { .. } => Some(ContentType::Yaml),
+        _ => None,
+    }
+}
+
+/// Converts `text` from one data format to another.
+pub fn convert(text: &str, from: &ContentType, to: &ContentType) -> Result<String, String> {
+    let unsupported = |content_type: &ContentType| match content_type {
+        ContentType::Xml => {
+            "XML has no single mapping to JSON, YAML or TOML and can't be converted".to_string()
+        }
+        other => format!("Can't convert {other}, only JSON, YAML and TOML"),
+    };
+    let from = data_format(from).ok_or_else(|| unsupported(from))?;
+    let to = data_format(to).ok_or_else(|| unsupported(to))?;
+    let value = read(text, &from)?;
+    write(&value, &to)
+}
+
+fn read(text: &str, format: &ContentType) -> Result<Value, String> {
+    match format {
+        ContentType::Json => serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {e}")),
+        ContentType::Yaml => yaml::parse(text).map_err(|e| format!("Invalid YAML: {e}")),
+        _ => {
+            let value = toml::from_str(text)
+                .map_err(|e| format!("Invalid TOML: {}", e.to_string().trim_end()))?;
+            Ok(toml_datetimes(value))
+        }
+    }
+}
+
+/// Replaces the tables the toml crate uses for datetimes with the
+/// datetimes as written.
+fn toml_datetimes(value: Value) -> Value {
+    match value {
+        Value::Object(mut map) => {
+            if map.len() == 1 {
+                if let Some(Value::String(datetime)) = map.remove(TOML_DATETIME) {
+                    return Value::String(datetime);
+                }
+            }
+            Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, toml_datetimes(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(toml_datetimes).collect()),
+        other => other,
+    }
+}
+
+fn write(value: &Value, format: &ContentType) -> Result<String, String> {
+    match format {
+        ContentType::Json => {
+            let mut out = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+            out.push('\n');
+            Ok(out)
+        }
+        ContentType::Yaml => yaml::to_string(value),
+        _ => {
+            let Value::Object(table) = value else {
+                return Err(format!(
+                    "TOML needs a table at the top level, not {}",
+                    kind(value)
+                ));
+            };
+            check_toml(table, "")?;
+            toml::to_string(table).map_err(|e| format!("Can't write TOML: {e}"))
+        }
+    }
+}
+
+/// TOML has no null; reports the first one with the path to it.
+fn check_toml(table: &Map<String, Value>, path: &str) -> Result<(), String> {
+    for (key, value) in table {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        check_toml_value(value, &path)?;
+    }
+    Ok(())
+}
+
+fn check_toml_value(value: &Value, path: &str) -> Result<(), String> {
+    match value {
+        Value::Null => Err(format!("TOML has no null, found one at '{path}'")),
+        Value::Object(table) => check_toml(table, path),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, item)| check_toml_value(item, &format!("{path}[{i}]"))),
+        _ => Ok(()),
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "a table",
+    }
+}
+
+#[cfg(test)]
+#[path = "convert_tests.rs"]
+mod tests;
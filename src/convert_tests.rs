@@ -0,0 +1,97 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::convert::*;
+use crate::detect::ContentType::{self, Json, Markdown, Toml, Xml, Yaml};
+
+fn conv(text: &str, from: ContentType, to: ContentType) -> String {
+    convert(text, &from, &to).unwrap()
+}
+
+#[test]
+fn test_json_to_yaml() {
+    let json = r#"{"name": "web", "ports": [80, 443], "tls": {"enabled": true}}"#;
+    assert_eq!(
+        conv(json, Json, Yaml),
+        "name: web\nports:\n- 80\n- 443\ntls:\n  enabled: true\n"
+    );
+}
+
+#[test]
+fn test_yaml_to_json() {
+    let yaml = "b: 1\na:\n  - x\n  - null\n";
+    assert_eq!(
+        conv(yaml, Yaml, Json),
+        "{\n  \"b\": 1,\n  \"a\": [\n    \"x\",\n    null\n  ]\n}\n"
+    );
+}
+
+#[test]
+fn test_to_toml() {
+    // plain keys come before tables, whatever the source order
+    let json = r#"{"server": {"port": 80}, "name": "web", "tags": ["a"]}"#;
+    assert_eq!(
+        conv(json, Json, Toml),
+        "name = \"web\"\ntags = [\"a\"]\n\n[server]\nport = 80\n"
+    );
+    let yaml = "title: x\nowner:\n  name: Ada\n";
+    assert_eq!(
+        conv(yaml, Yaml, Toml),
+        "title = \"x\"\n\n[owner]\nname = \"Ada\"\n"
+    );
+}
+
+#[test]
+fn test_from_toml() {
+    let toml = "z = 1\na = 2020-01-02T03:04:05Z\n\n[table]\nlist = [1, 2]\n";
+    assert_eq!(
+        conv(toml, Toml, Yaml),
+        "z: 1\na: 2020-01-02T03:04:05Z\ntable:\n  list:\n  - 1\n  - 2\n"
+    );
+    assert_eq!(
+        conv(toml, Toml, Json),
+        "{\n  \"z\": 1,\n  \"a\": \"2020-01-02T03:04:05Z\",\n  \"table\": {\n    \"list\": [\n      1,\n      2\n    ]\n  }\n}\n"
+    );
+}
+
+#[test]
+fn test_kubernetes_is_yaml() {
+    let kind = ContentType::Kubernetes {
+        kind: "Service".to_string(),
+    };
+    assert_eq!(
+        convert("kind: Service\n", &kind, &Json).unwrap(),
+        "{\n  \"kind\": \"Service\"\n}\n"
+    );
+    assert_eq!(data_format(&kind), Some(Yaml));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        convert("[1, 2]", &Json, &Toml).unwrap_err(),
+        "TOML needs a table at the top level, not an array"
+    );
+    assert_eq!(
+        convert(r#"{"a": {"b": [1, null]}}"#, &Json, &Toml).unwrap_err(),
+        "TOML has no null, found one at 'a.b[1]'"
+    );
+    assert_eq!(
+        convert("<a/>", &Xml, &Json).unwrap_err(),
+        "XML has no single mapping to JSON, YAML or TOML and can't be converted"
+    );
+    assert_eq!(
+        convert("# hi", &Markdown, &Json).unwrap_err(),
+        "Can't convert Markdown, only JSON, YAML and TOML"
+    );
+    assert!(convert("{", &Json, &Yaml)
+        .unwrap_err()
+        .starts_with("Invalid JSON: "));
+    assert_eq!(
+        convert("a: 1\na: 2\n", &Yaml, &Json).unwrap_err(),
+        "Invalid YAML: line 1, column 1: duplicate entry with key \"a\""
+    );
+    assert!(convert("a = ", &Toml, &Json)
+        .unwrap_err()
+        .starts_with("Invalid TOML: "));
+}
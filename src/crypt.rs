@@ -0,0 +1,119 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::output::Finish;
+use age::secrecy::SecretString;
+use age::stream::StreamWriter;
+use age::{x25519, Decryptor, Encryptor, IdentityFile};
+use std::env;
+use std::io::{self, Read};
+use std::iter;
+
+pub const EXTENSION: &str = "age";
+
+const PASSPHRASE_ENV: &str = "C2F_PASSPHRASE";
+
+pub enum EncryptKey {
+    Passphrase(SecretString),
+    Recipients(Vec<x25519::Recipient>),
+}
+
+impl EncryptKey {
+    /// Uses the given recipients if any, otherwise asks for a passphrase.
+    pub fn resolve(recipients: &[String]) -> Result<Self, String> {
+        if recipients.is_empty() {
+            return read_passphrase(true).map(EncryptKey::Passphrase);
+        }
+        recipients
+            .iter()
+            .map(|r| {
+                r.trim()
+                    .parse::<x25519::Recipient>()
+                    .map_err(|e| format!("Invalid recipient '{r}': {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(EncryptKey::Recipients)
+    }
+}
+
+/// Reads the passphrase from `C2F_PASSPHRASE`, or prompts on the terminal.
+pub fn read_passphrase(confirm: bool) -> Result<SecretString, String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Ok(SecretString::from(passphrase));
+    }
+
+    let prompt = |text: &str| {
+        rpassword::prompt_password(text).map_err(|e| format!("Failed to read passphrase: {e}"))
+    };
+    let passphrase = prompt("Passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err("Passphrase cannot be empty".to_string());
+    }
+    if confirm && prompt("Confirm passphrase: ")? != passphrase {
+        return Err("Passphrases do not match".to_string());
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+/// Encrypts everything written to the age format.
+pub fn encryptor<'a>(
+    writer: Box<dyn Finish + 'a>,
+    key: &EncryptKey,
+) -> Result<Box<dyn Finish + 'a>, String> {
+    let encryptor = match key {
+        EncryptKey::Passphrase(passphrase) => Encryptor::with_user_passphrase(passphrase.clone()),
+        EncryptKey::Recipients(recipients) => {
+            Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))
+                .map_err(|e| format!("Failed to encrypt: {e}"))?
+        }
+    };
+    let writer = encryptor
+        .wrap_output(writer)
+        .map_err(|e| format!("Failed to encrypt: {e}"))?;
+    Ok(Box::new(writer))
+}
+
+impl Finish for StreamWriter<Box<dyn Finish + '_>> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        StreamWriter::finish(*self)?.finish()
+    }
+}
+
+/// Decrypts an age file, using the identity file when given, otherwise a
+/// passphrase.
+pub fn decrypt(ciphertext: &[u8], identity_file: Option<&str>) -> Result<Vec<u8>, String> {
+    let decryptor =
+        Decryptor::new_buffered(ciphertext).map_err(|e| format!("Failed to decrypt: {e}"))?;
+
+    let reader = if decryptor.is_scrypt() {
+        let identity = age::scrypt::Identity::new(read_passphrase(false)?);
+        decryptor.decrypt(iter::once(&identity as &dyn age::Identity))
+    } else {
+        let path = identity_file
+            .ok_or_else(|| "File is encrypted to a key; pass --identity=<file>".to_string())?;
+        let path = &expand_home(path);
+        let identities = IdentityFile::from_file(path.to_string())
+            .map_err(|e| format!("Failed to read identity file '{path}': {e}"))?
+            .into_identities()
+            .map_err(|e| format!("Failed to read identity file '{path}': {e}"))?;
+        decryptor.decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))
+    };
+
+    let mut plaintext = Vec::new();
+    reader
+        .map_err(|e| format!("Failed to decrypt: {e}"))?
+        .read_to_end(&mut plaintext)
+        .map_err(|e| format!("Failed to decrypt: {e}"))?;
+    Ok(plaintext)
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "crypt_tests.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::compress::{self, Compression};
+use crate::crypt::*;
+use age::secrecy::{ExposeSecret, SecretString};
+use age::x25519;
+use std::io::{self, Write};
+
+fn encrypt(plaintext: &[u8], key: &EncryptKey) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut writer = encryptor(Box::new(io::BufWriter::new(&mut out)), key)?;
+    writer
+        .write_all(plaintext)
+        .and_then(|_| writer.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+#[test]
+fn test_recipient_round_trip() {
+    let identity = x25519::Identity::generate();
+    let recipient = identity.to_public().to_string();
+
+    let key = EncryptKey::resolve(&[recipient]).unwrap();
+    let ciphertext = encrypt(b"{\"token\": \"abc\"}", &key).unwrap();
+    assert!(ciphertext.starts_with(b"age-encryption.org/v1"));
+
+    let mut identity_file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(identity_file, "{}", identity.to_string().expose_secret()).unwrap();
+    let path = identity_file.path().to_str().unwrap();
+
+    let plaintext = decrypt(&ciphertext, Some(path)).unwrap();
+    assert_eq!(plaintext, b"{\"token\": \"abc\"}");
+}
+
+#[test]
+fn test_recipient_requires_identity() {
+    let recipient = x25519::Identity::generate().to_public().to_string();
+    let key = EncryptKey::resolve(&[recipient]).unwrap();
+    let ciphertext = encrypt(b"secret", &key).unwrap();
+
+    assert!(decrypt(&ciphertext, None)
+        .unwrap_err()
+        .contains("--identity"));
+}
+
+#[test]
+fn test_passphrase_encryption() {
+    let key = EncryptKey::Passphrase(SecretString::from("correct horse".to_string()));
+    let ciphertext = encrypt(b"secret", &key).unwrap();
+    assert!(!ciphertext.windows(6).any(|w| w == b"secret"));
+}
+
+#[test]
+fn test_invalid_recipient() {
+    let err = match EncryptKey::resolve(&["age1invalid".to_string()]) {
+        Err(e) => e,
+        Ok(_) => panic!("expected invalid recipient"),
+    };
+    assert!(err.contains("Invalid recipient"));
+}
+
+#[test]
+fn test_decrypt_rejects_plaintext() {
+    assert!(decrypt(b"not an age file", None).is_err());
+}
+
+#[test]
+fn test_compressed_round_trip() {
+    // saving chains content -> compression -> encryption
+    let identity = x25519::Identity::generate();
+    let key = EncryptKey::resolve(&[identity.to_public().to_string()]).unwrap();
+    let mut ciphertext = Vec::new();
+    let writer = encryptor(Box::new(io::BufWriter::new(&mut ciphertext)), &key).unwrap();
+    let mut writer = compress::encoder(writer, Compression::Zstd).unwrap();
+    writer.write_all(b"a,b\n1,2\n").unwrap();
+    writer.finish().unwrap();
+
+    let mut identity_file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(identity_file, "{}", identity.to_string().expose_secret()).unwrap();
+    let path = identity_file.path().to_str().unwrap();
+
+    let plaintext = decrypt(&ciphertext, Some(path)).unwrap();
+    let compression = Compression::sniff(&plaintext).unwrap();
+    assert_eq!(compression, Compression::Zstd);
+    assert_eq!(
+        compress::decompress(&plaintext, compression).unwrap(),
+        b"a,b\n1,2\n"
+    );
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType;
+use c2f::CustomType;
+use regex::Regex;
+
+/// A `[[custom_types]]` entry from the config, with its regex compiled.
+pub struct CustomDetector {
+    pub content_type: ContentType,
+    pub priority: i32,
+    regex: Option<Regex>,
+    prefix: Option<String>,
+    shebang: Option<String>,
+    keywords: Vec<String>,
+    min_keywords: usize,
+    json: bool,
+}
+
+impl CustomDetector {
+    /// Compiles config entries, highest priority first. Entries with equal
+    /// priority keep their order from the config file.
+    pub fn compile(types: &[CustomType]) -> Result<Vec<CustomDetector>, String> {
+        let mut detectors = types
+            .iter()
+            .map(CustomDetector::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        detectors.sort_by_key(|d| std::cmp::Reverse(d.priority));
+        Ok(detectors)
+    }
+
+    fn new(custom: &CustomType) -> Result<CustomDetector, String> {
+        let invalid = |reason: &str| format!("Invalid custom type '{}': {reason}", custom.name);
+        if custom.name.trim().is_empty() {
+            return Err("Invalid custom type: 'name' is required".to_string());
+        }
+        let extension = custom.extension.trim_start_matches('.');
+        if extension.is_empty() || extension.contains(['/', '\\']) {
+            return Err(invalid("'extension' must be a plain file extension"));
+        }
+
+        let regex = match &custom.regex {
+            Some(pattern) => {
+                // (?m) so ^ and $ work per line, which is what rules usually mean
+                Some(Regex::new(&format!("(?m){pattern}")).map_err(|e| invalid(&e.to_string()))?)
+            }
+            None => None,
+        };
+        let min_keywords = custom.min_keywords.unwrap_or(1);
+        if custom.min_keywords.is_some() && min_keywords > custom.keywords.len() {
+            return Err(invalid(
+                "'min_keywords' is larger than the number of keywords",
+            ));
+        }
+
+        let detector = CustomDetector {
+            content_type: ContentType::Custom {
+                name: custom.name.clone(),
+                extension: extension.to_string(),
+            },
+            priority: custom.priority,
+            regex,
+            prefix: custom.prefix.clone(),
+            shebang: custom.shebang.clone(),
+            keywords: custom.keywords.clone(),
+            min_keywords,
+            json: custom.json,
+        };
+        if !detector.has_rules() {
+            // a type without rules would claim everything
+            return Err(invalid("at least one match rule is required"));
+        }
+        Ok(detector)
+    }
+
+    fn has_rules(&self) -> bool {
+        self.regex.is_some()
+            || self.prefix.is_some()
+            || self.shebang.is_some()
+            || !self.keywords.is_empty()
+            || self.json
+    }
+
+    /// Whether `text` (already trimmed) satisfies every rule that is set.
+    pub fn matches(&self, text: &str) -> bool {
+        if let Some(prefix) = &self.prefix {
+            if !text.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(interpreter) = &self.shebang {
+            let first_line = text.lines().next().unwrap_or_default();
+            // matches `#!/usr/bin/nix-shell` and `#!/usr/bin/env nix-shell`
+            let suffix = format!("/{interpreter}");
+            let named = first_line.strip_prefix("#!").map_or(false, |line| {
+                line.split_whitespace()
+                    .any(|word| word == interpreter || word.ends_with(&suffix))
+            });
+            if !named {
+                return false;
+            }
+        }
+        if !self.keywords.is_empty() {
+            let found = self
+                .keywords
+                .iter()
+                .filter(|k| text.contains(k.as_str()))
+                .count();
+            if found < self.min_keywords {
+                return false;
+            }
+        }
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(text) {
+                return false;
+            }
+        }
+        if self.json && serde_json::from_str::<serde_json::Value>(text).is_err() {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+#[path = "custom_tests.rs"]
+mod tests;
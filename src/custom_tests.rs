@@ -0,0 +1,140 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::custom::*;
+use crate::detect::{detect_text_type_with, ContentType, DetectOptions};
+use c2f::CustomType;
+
+fn custom(name: &str, extension: &str) -> CustomType {
+    CustomType {
+        name: name.to_string(),
+        extension: extension.to_string(),
+        ..Default::default()
+    }
+}
+
+fn compile_one(custom: CustomType) -> CustomDetector {
+    CustomDetector::compile(&[custom]).unwrap().pop().unwrap()
+}
+
+#[test]
+fn test_regex_rule() {
+    let terraform = compile_one(CustomType {
+        regex: Some(r#"^(resource|variable|provider) ""#.to_string()),
+        ..custom("Terraform", "tf")
+    });
+    assert!(terraform.matches("# vpc\nresource \"aws_vpc\" \"main\" {\n}"));
+    assert!(!terraform.matches("resources: 3"));
+}
+
+#[test]
+fn test_prefix_and_shebang_rules() {
+    let proto = compile_one(CustomType {
+        prefix: Some("syntax = \"proto".to_string()),
+        ..custom("Protobuf", "proto")
+    });
+    assert!(proto.matches("syntax = \"proto3\";\nmessage Foo {}"));
+    assert!(!proto.matches("message Foo {}"));
+
+    let nix = compile_one(CustomType {
+        shebang: Some("nix-shell".to_string()),
+        ..custom("Nix shell", "nix")
+    });
+    assert!(nix.matches("#!/usr/bin/env nix-shell\n{ pkgs }: pkgs.hello"));
+    assert!(nix.matches("#!/run/current-system/sw/bin/nix-shell -i bash"));
+    assert!(!nix.matches("#!/bin/bash\necho nix-shell"));
+    assert!(!nix.matches("{ pkgs }: pkgs.hello"));
+}
+
+#[test]
+fn test_keyword_rule() {
+    let nix = compile_one(CustomType {
+        keywords: vec![
+            "mkDerivation".to_string(),
+            "pkgs".to_string(),
+            "inherit".to_string(),
+        ],
+        min_keywords: Some(2),
+        ..custom("Nix", "nix")
+    });
+    assert!(nix.matches("{ pkgs }: pkgs.stdenv.mkDerivation { }"));
+    assert!(!nix.matches("pkgs only"));
+}
+
+#[test]
+fn test_json_rule_combines_with_others() {
+    let notebook = compile_one(CustomType {
+        json: true,
+        keywords: vec!["\"nbformat\"".to_string()],
+        ..custom("Notebook", "ipynb")
+    });
+    assert!(notebook.matches(r#"{"cells": [], "nbformat": 4}"#));
+    assert!(!notebook.matches(r#"{"cells": [], "nbformat": 4"#));
+    assert!(!notebook.matches(r#"{"cells": []}"#));
+}
+
+#[test]
+fn test_invalid_custom_types() {
+    let error = |custom| match CustomDetector::compile(&[custom]) {
+        Err(e) => e,
+        Ok(_) => panic!("expected an error"),
+    };
+    assert!(error(custom("Empty", "x")).contains("at least one match rule"));
+    assert!(error(CustomType {
+        regex: Some("(".to_string()),
+        ..custom("Broken", "x")
+    })
+    .contains("Broken"));
+    assert!(error(CustomType {
+        keywords: vec!["a".to_string()],
+        ..custom("Bad path", "../x")
+    })
+    .contains("extension"));
+    assert!(error(CustomType {
+        keywords: vec!["a".to_string()],
+        min_keywords: Some(2),
+        ..custom("Too many", "x")
+    })
+    .contains("min_keywords"));
+}
+
+#[test]
+fn test_priority_against_builtins() {
+    let rule = |name: &str, priority| CustomType {
+        priority,
+        keywords: vec!["resource".to_string()],
+        ..custom(name, "tf")
+    };
+    // recognised as HCL by the built-in detectors
+    let text = "resource \"aws_vpc\" \"main\" {\n  tags = [\"a\"]\n}";
+    let detect = |types: &[CustomType]| {
+        let custom = CustomDetector::compile(types).unwrap();
+        let options = DetectOptions {
+            custom: &custom,
+            ..Default::default()
+        };
+        detect_text_type_with(text, &options).to_string()
+    };
+
+    // non-negative priorities run before the built-in detectors
+    assert_eq!(detect(&[rule("Terraform", 0)]), "Terraform");
+    // the highest priority wins between custom types
+    assert_eq!(
+        detect(&[rule("Low", 1), rule("High", 5)]),
+        "High".to_string()
+    );
+    // negative priorities only apply to what would be plain text
+    assert_eq!(detect(&[rule("Fallback", -1)]), "Terraform (HCL)");
+    let fallback = CustomDetector::compile(&[rule("Fallback", -1)]).unwrap();
+    let options = DetectOptions {
+        custom: &fallback,
+        ..Default::default()
+    };
+    assert_eq!(
+        detect_text_type_with("just a resource", &options),
+        ContentType::Custom {
+            name: "Fallback".to_string(),
+            extension: "tf".to_string()
+        }
+    );
+}
@@ -1,8 +1,12 @@
 // Copyright (c) 2025 balintb - https://github.com/balintb/c2f
 // Licensed under the MIT License
 
+use crate::classifier;
+use crate::custom::CustomDetector;
+use crate::scan::{self, Scan};
 use arboard::{Clipboard, ImageData};
 use std::fmt;
+use std::io::{self, Write};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ContentType {
@@ -15,7 +19,14 @@ pub enum ContentType {
     Yaml,
     Toml,
     Csv,
+    Tsv,
     Sql,
+    Hcl,
+    Protobuf,
+    GraphQl,
+    JupyterNotebook,
+    // saved as yaml, named after the manifest's kind
+    Kubernetes { kind: String },
 
     // languages
     Rust,
@@ -33,6 +44,17 @@ pub enum ContentType {
     Php,
     Swift,
     Kotlin,
+    Jsx,
+    Tsx,
+    Nix,
+    Lua,
+    Perl,
+    R,
+    Scala,
+    Haskell,
+    Elixir,
+    Dart,
+    Zig,
 
     // markup, styles
     Html,
@@ -40,6 +62,9 @@ pub enum ContentType {
     Latex,
     Css,
     Scss,
+    Svg,
+    Vue,
+    Svelte,
 
     // other config
     Dockerfile,
@@ -47,6 +72,11 @@ pub enum ContentType {
     Makefile,
     DotEnv,
     Ini,
+    Diff,
+    Log,
+
+    // user-defined in config.toml
+    Custom { name: String, extension: String },
 
     // plaintext fallback
     PlainText,
@@ -61,7 +91,14 @@ impl fmt::Display for ContentType {
             ContentType::Yaml => "YAML",
             ContentType::Toml => "TOML",
             ContentType::Csv => "CSV",
+            ContentType::Tsv => "TSV",
             ContentType::Sql => "SQL",
+            ContentType::Hcl => "Terraform (HCL)",
+            ContentType::Protobuf => "Protocol Buffers",
+            ContentType::GraphQl => "GraphQL",
+            ContentType::JupyterNotebook => "Jupyter notebook",
+            ContentType::Kubernetes { kind } if kind.is_empty() => "Kubernetes manifest",
+            ContentType::Kubernetes { kind } => return write!(f, "Kubernetes {kind}"),
             ContentType::Rust => "Rust",
             ContentType::Python => "Python",
             ContentType::JavaScript => "JavaScript",
@@ -77,16 +114,33 @@ impl fmt::Display for ContentType {
             ContentType::Php => "PHP",
             ContentType::Swift => "Swift",
             ContentType::Kotlin => "Kotlin",
+            ContentType::Jsx => "JSX",
+            ContentType::Tsx => "TSX",
+            ContentType::Nix => "Nix",
+            ContentType::Lua => "Lua",
+            ContentType::Perl => "Perl",
+            ContentType::R => "R",
+            ContentType::Scala => "Scala",
+            ContentType::Haskell => "Haskell",
+            ContentType::Elixir => "Elixir",
+            ContentType::Dart => "Dart",
+            ContentType::Zig => "Zig",
             ContentType::Html => "HTML",
             ContentType::Markdown => "Markdown",
             ContentType::Latex => "LaTeX",
             ContentType::Css => "CSS",
             ContentType::Scss => "SCSS",
+            ContentType::Svg => "SVG",
+            ContentType::Vue => "Vue component",
+            ContentType::Svelte => "Svelte component",
             ContentType::Dockerfile => "Dockerfile",
             ContentType::GitIgnore => "Git ignore",
             ContentType::Makefile => "Makefile",
             ContentType::DotEnv => "Environment file",
             ContentType::Ini => "INI config",
+            ContentType::Diff => "Diff",
+            ContentType::Log => "Log",
+            ContentType::Custom { name, .. } => name,
             ContentType::PlainText => "Plain text",
         };
         write!(f, "{name}")
@@ -94,7 +148,101 @@ impl fmt::Display for ContentType {
 }
 
 impl ContentType {
-    pub fn extension(&self) -> &'static str {
+    pub const ALL: &'static [ContentType] = &[
+        ContentType::Image,
+        ContentType::Json,
+        ContentType::Xml,
+        ContentType::Yaml,
+        ContentType::Toml,
+        ContentType::Csv,
+        ContentType::Tsv,
+        ContentType::Sql,
+        ContentType::Hcl,
+        ContentType::Protobuf,
+        ContentType::GraphQl,
+        ContentType::JupyterNotebook,
+        ContentType::Kubernetes {
+            kind: String::new(),
+        },
+        ContentType::Rust,
+        ContentType::Python,
+        ContentType::JavaScript,
+        ContentType::TypeScript,
+        ContentType::Go,
+        ContentType::Java,
+        ContentType::CSharp,
+        ContentType::Cpp,
+        ContentType::C,
+        ContentType::Shell,
+        ContentType::PowerShell,
+        ContentType::Ruby,
+        ContentType::Php,
+        ContentType::Swift,
+        ContentType::Kotlin,
+        ContentType::Jsx,
+        ContentType::Tsx,
+        ContentType::Nix,
+        ContentType::Lua,
+        ContentType::Perl,
+        ContentType::R,
+        ContentType::Scala,
+        ContentType::Haskell,
+        ContentType::Elixir,
+        ContentType::Dart,
+        ContentType::Zig,
+        ContentType::Html,
+        ContentType::Markdown,
+        ContentType::Latex,
+        ContentType::Css,
+        ContentType::Scss,
+        ContentType::Svg,
+        ContentType::Vue,
+        ContentType::Svelte,
+        ContentType::Dockerfile,
+        ContentType::GitIgnore,
+        ContentType::Makefile,
+        ContentType::DotEnv,
+        ContentType::Ini,
+        ContentType::Diff,
+        ContentType::Log,
+        ContentType::PlainText,
+    ];
+
+    /// Looks up a type by name or extension, ignoring case, spaces and
+    /// punctuation, so `yaml`, `YAML`, `yml`, `py`, `c#` and `shell-script`
+    /// all resolve.
+    pub fn from_name(value: &str) -> Option<ContentType> {
+        let normalize = |s: &str| -> String {
+            s.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '#' || *c == '+')
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let value = normalize(value);
+        let alias = match value.as_str() {
+            "yml" => Some(ContentType::Yaml),
+            "text" | "plain" => Some(ContentType::PlainText),
+            "bash" | "shell" => Some(ContentType::Shell),
+            "image" => Some(ContentType::Image),
+            "dotenv" => Some(ContentType::DotEnv),
+            "terraform" | "hcl" => Some(ContentType::Hcl),
+            "protobuf" => Some(ContentType::Protobuf),
+            "notebook" | "jupyter" => Some(ContentType::JupyterNotebook),
+            "k8s" | "kubernetes" => Some(ContentType::Kubernetes {
+                kind: String::new(),
+            }),
+            "patch" => Some(ContentType::Diff),
+            _ => None,
+        };
+        alias.or_else(|| {
+            ContentType::ALL
+                .iter()
+                .find(|t| value == t.extension() || value == normalize(&t.to_string()))
+                .cloned()
+        })
+    }
+
+    pub fn extension(&self) -> &str {
         match self {
             ContentType::Image => "png",
             ContentType::Json => "json",
@@ -102,7 +250,13 @@ impl ContentType {
             ContentType::Yaml => "yaml",
             ContentType::Toml => "toml",
             ContentType::Csv => "csv",
+            ContentType::Tsv => "tsv",
             ContentType::Sql => "sql",
+            ContentType::Hcl => "tf",
+            ContentType::Protobuf => "proto",
+            ContentType::GraphQl => "graphql",
+            ContentType::JupyterNotebook => "ipynb",
+            ContentType::Kubernetes { .. } => "yaml",
             ContentType::Rust => "rs",
             ContentType::Python => "py",
             ContentType::JavaScript => "js",
@@ -118,32 +272,98 @@ impl ContentType {
             ContentType::Php => "php",
             ContentType::Swift => "swift",
             ContentType::Kotlin => "kt",
+            ContentType::Jsx => "jsx",
+            ContentType::Tsx => "tsx",
+            ContentType::Nix => "nix",
+            ContentType::Lua => "lua",
+            ContentType::Perl => "pl",
+            ContentType::R => "r",
+            ContentType::Scala => "scala",
+            ContentType::Haskell => "hs",
+            ContentType::Elixir => "ex",
+            ContentType::Dart => "dart",
+            ContentType::Zig => "zig",
             ContentType::Html => "html",
             ContentType::Markdown => "md",
             ContentType::Latex => "tex",
             ContentType::Css => "css",
             ContentType::Scss => "scss",
+            ContentType::Svg => "svg",
+            ContentType::Vue => "vue",
+            ContentType::Svelte => "svelte",
             ContentType::Dockerfile => "dockerfile",
             ContentType::GitIgnore => "gitignore",
             ContentType::Makefile => "makefile",
             ContentType::DotEnv => "env",
             ContentType::Ini => "ini",
+            ContentType::Diff => "patch",
+            ContentType::Log => "log",
+            ContentType::Custom { extension, .. } => extension,
             ContentType::PlainText => "txt",
         }
     }
+
+    /// Source code and configuration, where typography like curly quotes
+    /// is a copy-paste accident rather than part of the text. Prose formats,
+    /// tables, diffs and custom types aren't.
+    pub fn is_code(&self) -> bool {
+        !matches!(
+            self,
+            ContentType::Image
+                | ContentType::Csv
+                | ContentType::Tsv
+                | ContentType::JupyterNotebook
+                | ContentType::Html
+                | ContentType::Markdown
+                | ContentType::Latex
+                | ContentType::Diff
+                | ContentType::Log
+                | ContentType::Custom { .. }
+                | ContentType::PlainText
+        )
+    }
+
+    /// File name to use instead of `clipboard` when none is given.
+    pub fn file_stem(&self) -> Option<String> {
+        match self {
+            ContentType::Kubernetes { kind } => {
+                let stem: String = kind
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                    .collect();
+                Some(stem).filter(|stem| !stem.is_empty())
+            }
+            _ => None,
+        }
+    }
+}
+
+pub const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Classifies raw file contents: PNG images by signature, anything else as
+/// text. Invalid UTF-8 is replaced rather than rejected so binary junk still
+/// gets an answer (usually plain text).
+pub fn detect_bytes(bytes: &[u8], options: &DetectOptions) -> ContentType {
+    if bytes.starts_with(PNG_SIGNATURE) {
+        return ContentType::Image;
+    }
+    let window = scan::window_bytes(bytes, options.max_bytes);
+    detect_text_type_with(&String::from_utf8_lossy(&window), options)
 }
 
 pub enum ClipboardContent {
-    Image(Vec<u8>),
+    /// Raw RGBA pixels, encoded to PNG only as they are written out.
+    Image(ImageData<'static>),
     Text(String),
 }
 
 pub fn detect_content(
     clipboard: &mut Clipboard,
+    options: &DetectOptions,
 ) -> Result<(ContentType, ClipboardContent), String> {
     if let Ok(img) = clipboard.get_image() {
-        let png_bytes = image_to_png(img)?;
-        return Ok((ContentType::Image, ClipboardContent::Image(png_bytes)));
+        return Ok((ContentType::Image, ClipboardContent::Image(img)));
     }
 
     let text = clipboard
@@ -154,168 +374,271 @@ pub fn detect_content(
         return Err("Clipboard is empty".to_string());
     }
 
-    let content_type = detect_text_type(&text);
+    let content_type = detect_text_type_with(&text, options);
     Ok((content_type, ClipboardContent::Text(text)))
 }
 
-fn image_to_png(img: ImageData) -> Result<Vec<u8>, String> {
-    use image::{ImageBuffer, Rgba};
+/// Encodes an image as PNG straight into `writer`, a band of rows at a
+/// time, calling `progress` with the rows done so far and the total.
+pub fn write_png(
+    img: &ImageData,
+    writer: &mut dyn Write,
+    progress: &mut dyn FnMut(usize, usize),
+) -> io::Result<()> {
+    let row_bytes = img.width * 4;
+    if img.bytes.len() != row_bytes * img.height {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Image data does not match its size",
+        ));
+    }
 
-    let width = img.width as u32;
-    let height = img.height as u32;
-    let bytes = img.bytes;
+    let mut encoder = png::Encoder::new(writer, img.width as u32, img.height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut png = encoder.write_header()?;
+    let mut stream = png.stream_writer()?;
+
+    // report in steps of about 1%
+    let band = (img.height / 100).max(1);
+    for (i, rows) in img.bytes.chunks(band * row_bytes).enumerate() {
+        stream.write_all(rows)?;
+        progress(((i + 1) * band).min(img.height), img.height);
+    }
+    stream.finish()?;
+    png.finish()?;
+    Ok(())
+}
 
-    // buffer for rgba
-    let img_buffer = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, bytes.to_vec())
-        .ok_or_else(|| "Failed to create image buffer".to_string())?;
+/// Decodes PNG bytes back into clipboard image data.
+pub fn png_to_image(bytes: &[u8]) -> Result<ImageData<'static>, String> {
+    let img = image::load_from_memory_with_format(bytes, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to decode PNG: {e}"))?
+        .to_rgba8();
+
+    Ok(ImageData {
+        width: img.width() as usize,
+        height: img.height() as usize,
+        bytes: img.into_raw().into(),
+    })
+}
 
-    // png encode
-    let mut png_bytes = Vec::new();
-    img_buffer
-        .write_to(
-            &mut std::io::Cursor::new(&mut png_bytes),
-            image::ImageFormat::Png,
-        )
-        .map_err(|e| format!("Failed to encode PNG: {e}"))?;
+/// How detection runs: user-defined types and the sampling budget.
+#[derive(Clone, Copy)]
+pub struct DetectOptions<'a> {
+    pub custom: &'a [CustomDetector],
+    /// Bytes of a large input that get looked at, split between its start
+    /// and end (see `scan::window`). 0 looks at everything.
+    pub max_bytes: usize,
+}
 
-    Ok(png_bytes)
+impl Default for DetectOptions<'_> {
+    fn default() -> Self {
+        DetectOptions {
+            custom: &[],
+            max_bytes: c2f::DEFAULT_DETECT_MAX_BYTES,
+        }
+    }
 }
 
-pub fn detect_text_type(text: &str) -> ContentType {
-    let trimmed = text.trim();
+/// Like `detect_text_type`, but also tries user-defined types: those with a
+/// non-negative priority before the built-in detectors, the rest only when
+/// nothing built-in matched.
+pub fn detect_text_type_with(text: &str, options: &DetectOptions) -> ContentType {
+    let window = scan::window(text, options.max_bytes);
+    let trimmed = window.trim();
+    let (before, after): (Vec<_>, Vec<_>) = options.custom.iter().partition(|d| d.priority >= 0);
 
-    // strict parsers first
-    if detectors::data::is_json(trimmed) {
-        return ContentType::Json;
+    if let Some(detector) = before.iter().find(|d| d.matches(trimmed)) {
+        return detector.content_type.clone();
     }
-    if detectors::data::is_xml(trimmed) {
-        return ContentType::Xml;
+    match detect_text_type(trimmed) {
+        ContentType::PlainText => after
+            .iter()
+            .find(|d| d.matches(trimmed))
+            .map_or(ContentType::PlainText, |d| d.content_type.clone()),
+        content_type => content_type,
     }
+}
 
-    // config files (before yaml)
-    if detectors::special::is_dockerfile(trimmed) {
-        return ContentType::Dockerfile;
-    }
-    if detectors::special::is_gitignore(trimmed) {
-        return ContentType::GitIgnore;
-    }
-    if detectors::special::is_makefile(trimmed) {
-        return ContentType::Makefile;
-    }
+/// Runs the built-in detectors over all of `text`. `detect_text_type_with`
+/// samples large inputs down first.
+pub fn detect_text_type(text: &str) -> ContentType {
+    let trimmed = &Scan::new(text.trim());
 
-    // yaml after makefile
-    if detectors::data::is_yaml(trimmed) {
-        return ContentType::Yaml;
+    // a diff can contain any language, so it goes first
+    if detectors::special::is_diff(trimmed) {
+        return ContentType::Diff;
     }
-    if detectors::data::is_toml(trimmed) {
-        return ContentType::Toml;
+
+    // an explicit shebang or editor modeline beats any heuristic
+    if let Some(content_type) = detectors::hints::shebang(trimmed) {
+        return content_type;
     }
-    if detectors::data::is_csv(trimmed) {
-        return ContentType::Csv;
+    if let Some(content_type) = detectors::hints::modeline(trimmed) {
+        return content_type;
     }
-    if detectors::data::is_sql(trimmed) {
-        return ContentType::Sql;
+    // and so does a php file's opening tag
+    if trimmed.starts_with("<?php") {
+        return ContentType::Php;
     }
 
-    if detectors::special::is_dotenv(trimmed) {
-        return ContentType::DotEnv;
-    }
-    if detectors::special::is_ini(trimmed) {
-        return ContentType::Ini;
+    // nix expressions are often wrapped in braces, like json
+    if detectors::lang::is_nix(trimmed) {
+        return ContentType::Nix;
     }
 
-    // markup
-    if detectors::markup::is_html(trimmed) {
-        return ContentType::Html;
-    }
-    if detectors::markup::is_markdown(trimmed) {
-        return ContentType::Markdown;
+    // strict parsers first
+    if detectors::data::is_json(trimmed) {
+        if detectors::data::is_jupyter_notebook(trimmed) {
+            return ContentType::JupyterNotebook;
+        }
+        return ContentType::Json;
     }
-    if detectors::markup::is_latex(trimmed) {
-        return ContentType::Latex;
+    if detectors::markup::is_svg(trimmed) {
+        return ContentType::Svg;
     }
 
-    // languages
-    if detectors::lang::is_rust(trimmed) {
-        return ContentType::Rust;
-    }
-    if detectors::lang::is_python(trimmed) {
-        return ContentType::Python;
-    }
-    if detectors::lang::is_typescript(trimmed) {
-        return ContentType::TypeScript;
-    }
-    if detectors::lang::is_javascript(trimmed) {
-        return ContentType::JavaScript;
-    }
-    if detectors::lang::is_go(trimmed) {
-        return ContentType::Go;
-    }
-    if detectors::lang::is_java(trimmed) {
-        return ContentType::Java;
+    // components mix markup with script, check before xml and html
+    if detectors::markup::is_vue(trimmed) {
+        return ContentType::Vue;
     }
-    if detectors::lang::is_csharp(trimmed) {
-        return ContentType::CSharp;
+    if detectors::markup::is_svelte(trimmed) {
+        return ContentType::Svelte;
     }
-    if detectors::lang::is_cpp(trimmed) {
-        return ContentType::Cpp;
+    if detectors::lang::is_tsx(trimmed) {
+        return ContentType::Tsx;
     }
-    if detectors::lang::is_c(trimmed) {
-        return ContentType::C;
+    if detectors::lang::is_jsx(trimmed) {
+        return ContentType::Jsx;
     }
-    if detectors::lang::is_shell(trimmed) {
-        return ContentType::Shell;
-    }
-    if detectors::lang::is_powershell(trimmed) {
-        return ContentType::PowerShell;
-    }
-    if detectors::lang::is_ruby(trimmed) {
-        return ContentType::Ruby;
-    }
-    if detectors::lang::is_php(trimmed) {
-        return ContentType::Php;
-    }
-    if detectors::lang::is_swift(trimmed) {
-        return ContentType::Swift;
+
+    if detectors::data::is_xml(trimmed) {
+        return ContentType::Xml;
     }
-    if detectors::lang::is_kotlin(trimmed) {
-        return ContentType::Kotlin;
+
+    if let Some(kind) = detectors::data::kubernetes_kind(trimmed) {
+        return ContentType::Kubernetes { kind };
     }
 
-    if detectors::markup::is_scss(trimmed) {
-        return ContentType::Scss;
+    // everything below is a loose heuristic. A single match stands; between
+    // several the classifier decides, favouring the earlier ones, and with no
+    // match it names a type only when it is confident
+    let matched: Vec<ContentType> = RULES
+        .iter()
+        .filter(|(matches, _)| matches(trimmed))
+        .map(|(_, content_type)| content_type.clone())
+        .collect();
+    if let [only] = matched.as_slice() {
+        return only.clone();
     }
-    if detectors::markup::is_css(trimmed) {
-        return ContentType::Css;
+    match classifier::classify(trimmed, &matched) {
+        Some(p) if !matched.is_empty() || p.margin >= FALLBACK_MARGIN => p.content_type,
+        Some(_) => ContentType::PlainText,
+        None => matched.first().cloned().unwrap_or(ContentType::PlainText),
     }
-
-    ContentType::PlainText
 }
 
+/// How far ahead the classifier must be to name a type no rule matched.
+const FALLBACK_MARGIN: f32 = 0.15;
+
+type Detector = fn(&Scan) -> bool;
+
+/// Rule-based detectors that run after the strict checks above, in priority
+/// order: the classifier favours the first match most.
+const RULES: &[(Detector, ContentType)] = &[
+    // log lines look like yaml key-values
+    (detectors::special::is_log, ContentType::Log),
+    // config files (before yaml)
+    (detectors::special::is_dockerfile, ContentType::Dockerfile),
+    (detectors::special::is_gitignore, ContentType::GitIgnore),
+    (detectors::special::is_makefile, ContentType::Makefile),
+    // schema languages with `key = value` or `field: Type` lines that
+    // would otherwise pass as toml or yaml
+    (detectors::data::is_hcl, ContentType::Hcl),
+    (detectors::data::is_protobuf, ContentType::Protobuf),
+    (detectors::data::is_graphql, ContentType::GraphQl),
+    (detectors::lang::is_haskell, ContentType::Haskell),
+    // less common languages have distinctive markers but also lines like
+    // `x: Int` that the loose yaml check would claim
+    (detectors::lang::is_zig, ContentType::Zig),
+    (detectors::lang::is_dart, ContentType::Dart),
+    (detectors::lang::is_elixir, ContentType::Elixir),
+    (detectors::lang::is_scala, ContentType::Scala),
+    (detectors::lang::is_perl, ContentType::Perl),
+    (detectors::lang::is_lua, ContentType::Lua),
+    (detectors::lang::is_r, ContentType::R),
+    // spreadsheet rows, whose cells could hold anything below
+    (detectors::data::is_tsv, ContentType::Tsv),
+    // yaml after makefile
+    (detectors::data::is_yaml, ContentType::Yaml),
+    (detectors::data::is_toml, ContentType::Toml),
+    (detectors::data::is_csv, ContentType::Csv),
+    (detectors::data::is_sql, ContentType::Sql),
+    (detectors::special::is_dotenv, ContentType::DotEnv),
+    (detectors::special::is_ini, ContentType::Ini),
+    // markup
+    (detectors::markup::is_html, ContentType::Html),
+    (detectors::markup::is_markdown, ContentType::Markdown),
+    (detectors::markup::is_latex, ContentType::Latex),
+    // languages
+    (detectors::lang::is_rust, ContentType::Rust),
+    (detectors::lang::is_python, ContentType::Python),
+    (detectors::lang::is_typescript, ContentType::TypeScript),
+    (detectors::lang::is_javascript, ContentType::JavaScript),
+    (detectors::lang::is_go, ContentType::Go),
+    (detectors::lang::is_java, ContentType::Java),
+    (detectors::lang::is_csharp, ContentType::CSharp),
+    (detectors::lang::is_cpp, ContentType::Cpp),
+    (detectors::lang::is_c, ContentType::C),
+    (detectors::lang::is_shell, ContentType::Shell),
+    (detectors::lang::is_powershell, ContentType::PowerShell),
+    (detectors::lang::is_ruby, ContentType::Ruby),
+    (detectors::lang::is_php, ContentType::Php),
+    (detectors::lang::is_swift, ContentType::Swift),
+    (detectors::lang::is_kotlin, ContentType::Kotlin),
+    (detectors::markup::is_scss, ContentType::Scss),
+    (detectors::markup::is_css, ContentType::Css),
+];
+
 pub mod detectors {
     pub mod data {
-        pub fn is_json(text: &str) -> bool {
-            (text.starts_with('{') && text.ends_with('}'))
-                || (text.starts_with('[') && text.ends_with(']'))
+        use crate::scan::Scan;
+
+        pub fn is_json(text: &Scan) -> bool {
+            let bracketed = (text.starts_with('{') && text.ends_with('}'))
+                || (text.starts_with('[') && text.ends_with(']'));
+            // what follows the opening brackets starts a value, so a toml
+            // `[[bin]]` table isn't taken for an array
+            let inner = text.trim_start_matches(|c: char| c == '[' || c.is_whitespace());
+            let first_value = match inner.strip_prefix('{') {
+                Some(object) => object.trim_start().starts_with(['"', '}']),
+                None => inner.starts_with(|c: char| {
+                    matches!(c, '"' | '-' | ']' | 't' | 'f' | 'n') || c.is_ascii_digit()
+                }),
+            };
+            bracketed && first_value
         }
 
-        pub fn is_xml(text: &str) -> bool {
+        pub fn is_xml(text: &Scan) -> bool {
             if text.starts_with("<?xml") {
                 return true;
             }
 
             // html-like content
-            let lower = text.to_lowercase();
-            if lower.contains("<div")
-                || lower.contains("<span")
-                || lower.contains("<p>")
-                || lower.contains("<body")
-                || lower.contains("<html")
-                || lower.contains("<!doctype")
-                || lower.contains("<h1")
-                || lower.contains("<h2")
-                || lower.contains("<a ")
-                || lower.contains("<img")
+            if text.contains_ignore_case("<div")
+                || text.contains_ignore_case("<span")
+                || text.contains_ignore_case("<p>")
+                || text.contains_ignore_case("<body")
+                || text.contains_ignore_case("<html")
+                || text.contains_ignore_case("<!doctype")
+                || text.contains_ignore_case("<h1")
+                || text.contains_ignore_case("<h2")
+                || text.contains_ignore_case("<a ")
+                || text.contains_ignore_case("<img")
+                || text.contains_ignore_case("<table")
+                || text.contains_ignore_case("<ul>")
+                || text.contains("<?php")
             {
                 return false; // not html, not xml
             }
@@ -324,7 +647,7 @@ pub mod detectors {
             text.starts_with('<') && text.contains("</")
         }
 
-        pub fn is_yaml(text: &str) -> bool {
+        pub fn is_yaml(text: &Scan) -> bool {
             if text.starts_with("---") {
                 return true;
             }
@@ -334,36 +657,172 @@ pub mod detectors {
                 trimmed.starts_with("- ") || trimmed.starts_with("* ")
             });
 
+            // keys are single words; `Signed by: resident` is prose, and
+            // `f(x: 1)`, `name?: string;` or `theme: {` are code
             let has_key_value = text.lines().any(|l| {
-                let trimmed = l.trim();
-                trimmed.contains(": ") && !trimmed.starts_with("//") && !trimmed.starts_with("#")
+                let trimmed = l.trim().trim_start_matches("- ");
+                !trimmed.starts_with("//")
+                    && !trimmed.starts_with('#')
+                    && !trimmed.ends_with([';', ',', '{'])
+                    && trimmed.split_once(": ").map_or(false, |(key, value)| {
+                        !key.is_empty()
+                            && key.chars().all(|c| {
+                                c.is_alphanumeric()
+                                    || matches!(c, '_' | '-' | '.' | '/' | '"' | '\'')
+                            })
+                            && !is_prose(value)
+                    })
             });
 
             has_key_value || (text.contains(":\n") && has_yaml_list)
         }
 
-        pub fn is_toml(text: &str) -> bool {
-            text.contains("[") && text.contains("]") && text.contains(" = ")
+        pub fn is_toml(text: &Scan) -> bool {
+            // strings are quoted, unlike ini's `profile = black` or hcl's
+            // `vpc_id = aws_vpc.main.id`, and nothing ends in `;` or `{`
+            let not_toml = text.lines().any(|l| {
+                l.split_once(" = ").map_or(false, |(_, value)| {
+                    let value = value.trim();
+                    // inline tables stay on one line
+                    value.ends_with([';', '{'])
+                        || value.starts_with(char::is_alphabetic)
+                            && !["true", "false", "inf", "nan"]
+                                .iter()
+                                .any(|word| value.starts_with(word))
+                })
+            });
+            text.contains("[") && text.contains("]") && text.contains(" = ") && !not_toml
         }
 
-        pub fn is_csv(text: &str) -> bool {
+        /// The same number of fields on each of the first lines, commas
+        /// in quoted fields aside.
+        pub fn is_csv(text: &Scan) -> bool {
             let lines: Vec<&str> = text.lines().take(3).collect();
-            lines.len() > 1 && lines.iter().all(|l| l.contains(','))
+            let commas = |line: &str| {
+                line.split('"')
+                    .step_by(2)
+                    .map(|unquoted| unquoted.matches(',').count())
+                    .sum::<usize>()
+            };
+            lines.len() > 1
+                && commas(lines[0]) > 0
+                && lines.iter().all(|l| commas(l) == commas(lines[0]))
+                && !lines.iter().any(|l| is_prose(l))
+        }
+
+        /// Runs on from one sentence into the next, which cells and values
+        /// rarely do.
+        fn is_prose(text: &str) -> bool {
+            text.match_indices(". ")
+                .any(|(i, _)| text[i + 2..].starts_with(|c: char| c.is_uppercase()))
+        }
+
+        /// Tab-separated rows, as copied from a spreadsheet: the same
+        /// number of tabs on each of the first lines, none of them
+        /// indentation.
+        pub fn is_tsv(text: &Scan) -> bool {
+            let lines: Vec<&str> = text.lines().take(5).collect();
+            let tabs = |line: &str| line.matches('\t').count();
+            lines.len() > 1
+                && tabs(lines[0]) > 0
+                && !lines[0].starts_with('\t')
+                && lines.iter().all(|line| tabs(line) == tabs(lines[0]))
+        }
+
+        pub fn is_sql(text: &Scan) -> bool {
+            text.contains_ignore_case("SELECT ")
+                || text.contains_ignore_case("INSERT ")
+                || text.contains_ignore_case("UPDATE ")
+                || text.contains_ignore_case("DELETE ")
+                || text.contains_ignore_case("CREATE TABLE")
+                || text.contains_ignore_case("ALTER TABLE")
+        }
+
+        pub fn is_jupyter_notebook(text: &Scan) -> bool {
+            text.starts_with('{') && text.contains("\"nbformat\"") && text.contains("\"cells\"")
+        }
+
+        /// The `kind` of a Kubernetes manifest, or `manifests` when a
+        /// multi-document file mixes kinds.
+        pub fn kubernetes_kind(text: &Scan) -> Option<String> {
+            let mut kinds = Vec::new();
+            for document in text.split("\n---") {
+                let top_level = |key: &str| {
+                    document
+                        .lines()
+                        .find_map(|l| l.strip_prefix(key))
+                        .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\''))
+                };
+                // the kind names the saved file, so nothing like `../x` gets through
+                let kind = top_level("kind:").map(|kind| {
+                    kind.chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                        .collect::<String>()
+                });
+                match (top_level("apiVersion:"), kind) {
+                    (Some(_), Some(kind)) if !kind.is_empty() => kinds.push(kind),
+                    _ => {}
+                }
+            }
+
+            let first = kinds.first()?;
+            if kinds.iter().all(|kind| kind == first) {
+                Some(first.clone())
+            } else {
+                Some("manifests".to_string())
+            }
+        }
+
+        pub fn is_hcl(text: &Scan) -> bool {
+            const BLOCKS: [&str; 7] = [
+                "resource \"",
+                "variable \"",
+                "provider \"",
+                "module \"",
+                "output \"",
+                "data \"",
+                "terraform {",
+            ];
+            text.lines()
+                .any(|l| BLOCKS.iter().any(|block| l.starts_with(block)) && l.ends_with('{'))
         }
 
-        pub fn is_sql(text: &str) -> bool {
-            let upper = text.to_uppercase();
-            upper.contains("SELECT ")
-                || upper.contains("INSERT ")
-                || upper.contains("UPDATE ")
-                || upper.contains("DELETE ")
-                || upper.contains("CREATE TABLE")
-                || upper.contains("ALTER TABLE")
+        pub fn is_protobuf(text: &Scan) -> bool {
+            text.starts_with("syntax = \"proto")
+                || (text.contains("message ") && text.contains(" = 1;"))
+                || (text.contains("service ") && text.contains("rpc "))
+        }
+
+        pub fn is_graphql(text: &Scan) -> bool {
+            let first = text.lines().next().unwrap_or_default();
+            let operation = ["query", "mutation", "subscription", "fragment "]
+                .iter()
+                .any(|op| first.starts_with(op))
+                && first.trim_end().ends_with('{');
+            if operation || first.starts_with("schema {") {
+                return true;
+            }
+
+            // type definitions: `type Query {` followed by `name: Type!` fields
+            let has_type_block = text.lines().any(|l| {
+                (l.starts_with("type ") || l.starts_with("input ") || l.starts_with("enum "))
+                    && l.ends_with('{')
+                    && !l.contains('=')
+            });
+            let has_typed_field = text.lines().any(|l| {
+                l.trim().split_once(": ").map_or(false, |(_, ty)| {
+                    let ty = ty.trim_start_matches('[');
+                    ty.starts_with(|c: char| c.is_ascii_uppercase()) && ty.ends_with(['!', ']'])
+                })
+            });
+            has_type_block && has_typed_field
         }
     }
 
     pub mod lang {
-        pub fn is_rust(text: &str) -> bool {
+        use crate::scan::Scan;
+
+        pub fn is_rust(text: &Scan) -> bool {
             text.contains("fn ")
                 || text.contains("impl ")
                 || text.contains("use ")
@@ -374,36 +833,61 @@ pub mod detectors {
                 || text.contains("match ")
         }
 
-        pub fn is_python(text: &str) -> bool {
+        pub fn is_python(text: &Scan) -> bool {
             text.starts_with("#!/usr/bin/env python")
                 || text.starts_with("#!/usr/bin/python")
-                || text.contains("import ")
-                || text.contains("from ")
+                // `import os` or `from x import y`, not javascript's
+                // `import { a } from 'b'` or a sentence with "from"
+                || text.lines().any(|l| {
+                    (l.starts_with("import ") && !l.contains(['{', '\'', '"']))
+                        || (l.starts_with("from ") && l.contains(" import "))
+                })
                 || text.contains("def ")
-                || text.contains("class ")
+                || text.lines().any(|l| {
+                    let l = l.trim();
+                    l.starts_with("class ") && l.ends_with(':')
+                })
                 || text.contains("if __name__")
-                || text.contains("print(")
+                // but not statements ending in `;`
+                || (text.contains("print(") && !text.lines().any(|l| l.trim_end().ends_with(';')))
         }
 
-        pub fn is_typescript(text: &str) -> bool {
+        pub fn is_typescript(text: &Scan) -> bool {
+            // `type Name = ...`, not hcl's `type = string`
+            let type_alias = text.contains("type ")
+                && text.lines().any(|l| {
+                    let l = l.trim_start();
+                    let l = l.strip_prefix("export ").unwrap_or(l);
+                    l.strip_prefix("type ").map_or(false, |rest| {
+                        rest.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    })
+                });
             text.contains("interface ")
-                || text.contains("type ")
-                || text.contains(": string")
-                || text.contains(": number")
-                || text.contains(": boolean")
+                || type_alias
+                || has_type_annotations(text)
                 || text.contains("enum ")
         }
 
-        pub fn is_javascript(text: &str) -> bool {
-            text.contains("const ")
+        pub fn is_javascript(text: &Scan) -> bool {
+            let script = text.contains("const ")
                 || text.contains("let ")
                 || text.contains("var ")
                 || text.contains("function ")
                 || text.contains("=>")
                 || text.contains("console.log(")
+                || text.contains("module.exports");
+            script && !has_type_annotations(text)
+        }
+
+        /// `name: string` and the like, which only TypeScript has.
+        fn has_type_annotations(text: &Scan) -> bool {
+            text.contains(": string")
+                || text.contains(": number")
+                || text.contains(": boolean")
+                || text.contains(": void")
         }
 
-        pub fn is_go(text: &str) -> bool {
+        pub fn is_go(text: &Scan) -> bool {
             text.contains("package ")
                 || text.contains("func ")
                 || text.contains("import (")
@@ -411,21 +895,29 @@ pub mod detectors {
                 || text.contains("type ") && text.contains(" struct")
         }
 
-        pub fn is_java(text: &str) -> bool {
+        pub fn is_java(text: &Scan) -> bool {
             text.contains("public class ")
                 || text.contains("private ")
                 || text.contains("public static void main")
                 || text.contains("import java.")
+                || text.contains("System.out.print")
+                // `catch (IOException e)`, where javascript has `catch (err)`
+                || text.lines().any(|l| {
+                    l.split_once("catch (").map_or(false, |(_, rest)| {
+                        rest.split(')').next().map_or(false, |caught| caught.contains(' '))
+                    })
+                })
         }
 
-        pub fn is_csharp(text: &str) -> bool {
+        pub fn is_csharp(text: &Scan) -> bool {
             text.contains("using System")
                 || text.contains("namespace ")
                 || text.contains("public class ")
+                || text.contains("public static class ")
                 || text.contains("static void Main")
         }
 
-        pub fn is_cpp(text: &str) -> bool {
+        pub fn is_cpp(text: &Scan) -> bool {
             text.contains("#include <")
                 || text.contains("std::")
                 || text.contains("cout <<")
@@ -433,23 +925,28 @@ pub mod detectors {
                 || text.contains("class ") && text.contains("::")
         }
 
-        pub fn is_c(text: &str) -> bool {
+        pub fn is_c(text: &Scan) -> bool {
             text.contains("#include <stdio.h>")
                 || text.contains("#include <stdlib.h>")
                 || text.contains("int main(")
                 || text.contains("void ")
+                || text.contains("const char *")
+                || text.contains("size_t ")
         }
 
-        pub fn is_shell(text: &str) -> bool {
+        pub fn is_shell(text: &Scan) -> bool {
             text.starts_with("#!/bin/bash")
                 || text.starts_with("#!/bin/sh")
                 || text.starts_with("#!/usr/bin/env bash")
-                || text.contains("echo ")
                 || text.contains("if [")
-                || text.contains("for ")
+                || text.lines().any(|l| {
+                    let l = l.trim_start();
+                    l.starts_with("echo ")
+                        || (l.starts_with("for ") && (l.contains(" in ") || l.contains("((")))
+                })
         }
 
-        pub fn is_powershell(text: &str) -> bool {
+        pub fn is_powershell(text: &Scan) -> bool {
             text.contains("$PSVersionTable")
                 || text.contains("Get-")
                 || text.contains("Set-")
@@ -457,38 +954,181 @@ pub mod detectors {
                 || text.starts_with("param(")
         }
 
-        pub fn is_ruby(text: &str) -> bool {
+        pub fn is_ruby(text: &Scan) -> bool {
             text.starts_with("#!/usr/bin/env ruby")
                 || text.contains("puts ")
                 || text.contains("require ")
                 || text.contains("def ")
                 || text.contains("class ") && text.contains("end")
+                // `describe Cart do` blocks
+                || text.lines().any(|l| l.trim_end().ends_with(" do") || l.contains(" do |"))
+                    && text.lines().any(|l| l.trim() == "end")
         }
 
-        pub fn is_php(text: &str) -> bool {
+        pub fn is_php(text: &Scan) -> bool {
             text.starts_with("<?php")
                 || text.contains("<?=")
                 || text.contains("echo ")
                 || text.contains("function ") && text.contains("$")
         }
 
-        pub fn is_swift(text: &str) -> bool {
+        pub fn is_swift(text: &Scan) -> bool {
             text.contains("import Foundation")
                 || text.contains("import UIKit")
                 || text.contains("func ")
                 || text.contains("var ") && text.contains(": ")
+                // implicit member expressions, `style = .medium`
+                || text.contains(" = .")
         }
 
-        pub fn is_kotlin(text: &str) -> bool {
-            text.contains("fun ")
+        pub fn is_kotlin(text: &Scan) -> bool {
+            let kotlin = text.contains("fun ")
                 || text.contains("val ")
                 || text.contains("var ") && text.contains(": ")
-                || text.contains("import kotlin.")
+                || text.contains("import kotlin.");
+            // scala declares its `val`s alongside `def`s
+            kotlin && !text.contains("def ")
+        }
+
+        fn has_jsx_markup(text: &Scan) -> bool {
+            // or markup on the line after any open paren, as in `render(`
+            let lines: Vec<&str> = text.lines().collect();
+            let wrapped = lines.windows(2).any(|pair| {
+                pair[0].trim_end().ends_with('(') && pair[1].trim_start().starts_with('<')
+            });
+            (text.contains("/>") || text.contains("</"))
+                && (text.contains("return (")
+                    || text.contains("=> (")
+                    || text.contains("return <")
+                    || wrapped)
+        }
+
+        pub fn is_jsx(text: &Scan) -> bool {
+            let react = text.contains("from 'react'")
+                || text.contains("from \"react\"")
+                || text.contains("React.")
+                || text.contains("className=")
+                || text.contains("useState(")
+                || text.contains("useEffect(")
+                // attributes holding expressions, `<li key={item.id}>`
+                || text.contains("={");
+            react && has_jsx_markup(text)
+        }
+
+        pub fn is_tsx(text: &Scan) -> bool {
+            is_jsx(text)
+                && (text.contains("interface ")
+                    || text.contains("type Props")
+                    || text.contains(": React.")
+                    || text.contains(": string")
+                    || text.contains(": number")
+                    || text.contains("useState<"))
+        }
+
+        pub fn is_nix(text: &Scan) -> bool {
+            text.contains("import <nixpkgs>")
+                || text.contains("mkDerivation")
+                || text.contains("with pkgs;")
+                || (text.starts_with('{') && text.contains("}:") && text.contains(" = "))
+        }
+
+        pub fn is_lua(text: &Scan) -> bool {
+            let has_end = text.lines().any(|l| l.trim() == "end");
+            text.contains("local function ")
+                || (text.lines().any(|l| l.starts_with("local ")) && has_end)
+                || (text.contains(" then") && text.contains("elseif "))
+                || (text.contains("function ") && text.contains(" then") && has_end)
+                || text.contains("require(\"") && text.contains("local ")
+        }
+
+        pub fn is_perl(text: &Scan) -> bool {
+            text.starts_with("#!/usr/bin/perl")
+                || text.starts_with("#!/usr/bin/env perl")
+                || text.contains("use strict;")
+                || text.contains("use warnings;")
+                || text.contains("my $")
+                || text.contains("my @")
+                || text.contains("my %")
+        }
+
+        pub fn is_r(text: &Scan) -> bool {
+            text.contains("library(")
+                || text.contains("<- function(")
+                || text.contains(" <- c(")
+                || text.contains("<- data.frame(")
+                // assigning a call, `fit <- lm(...)`
+                || text.lines().any(|l| {
+                    l.split_once(" <- ").map_or(false, |(name, value)| {
+                        let is_name = |s: &str| {
+                            !s.is_empty()
+                                && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                        };
+                        is_name(name) && value.split_once('(').map_or(false, |(f, _)| is_name(f))
+                    })
+                })
+        }
+
+        pub fn is_scala(text: &Scan) -> bool {
+            text.contains("import scala.")
+                || text.contains("case class ")
+                || text.contains("sealed trait ")
+                || text.contains("extends App")
+                || text.contains("def main(args: Array[String])")
+                || (text.contains("object ") && text.contains("def ") && text.contains(" = "))
+                || text.contains("implicit ")
+                || text.lines().any(|l| {
+                    let l = l.trim();
+                    // `def area: Double = ...`, where python's `def` lines end in `:`
+                    (l.starts_with("def ") && l.contains(" = ") && !l.ends_with(':'))
+                        // wildcard imports, `import spark.implicits._`
+                        || (l.starts_with("import ") && l.ends_with("._"))
+                })
+        }
+
+        pub fn is_haskell(text: &Scan) -> bool {
+            let has_signature = text.lines().any(|l| {
+                l.split_once(" :: ").map_or(false, |(name, _)| {
+                    !name.is_empty()
+                        && name
+                            .chars()
+                            .all(|c| c.is_alphanumeric() || c == '_' || c == '\'')
+                })
+            });
+            (text.starts_with("module ") && text.contains(" where"))
+                || text.contains("import qualified ")
+                || text.contains("main = do")
+                || has_signature
+        }
+
+        pub fn is_elixir(text: &Scan) -> bool {
+            let has_end = text.lines().any(|l| l.trim() == "end");
+            text.contains("defmodule ")
+                || (text.contains("|>") && has_end)
+                || (text.contains("def ")
+                    && text.contains(" do")
+                    && has_end
+                    && text.contains(":ok"))
+        }
+
+        pub fn is_dart(text: &Scan) -> bool {
+            text.contains("import 'package:")
+                || text.contains("import 'dart:")
+                || text.contains("Widget build(")
+                || text.contains(") async {")
+                || (text.contains("void main()")
+                    && text.contains("print(")
+                    && text.contains("final "))
+        }
+
+        pub fn is_zig(text: &Scan) -> bool {
+            text.contains("@import(\"") || text.contains("!void") || text.contains("comptime ")
         }
     }
 
     pub mod markup {
-        pub fn is_html(text: &str) -> bool {
+        use crate::scan::Scan;
+
+        pub fn is_html(text: &Scan) -> bool {
             text.contains("<!DOCTYPE")
                 || text.contains("<html")
                 || text.contains("<body")
@@ -499,13 +1139,15 @@ pub mod detectors {
                 || text.contains("<h2")
                 || text.contains("<a ")
                 || text.contains("<img")
+                || text.contains("<table")
+                || text.contains("<ul>")
                 || text.contains("</div>")
                 || text.contains("</span>")
                 || text.contains("</body>")
                 || text.contains("</html>")
         }
 
-        pub fn is_markdown(text: &str) -> bool {
+        pub fn is_markdown(text: &Scan) -> bool {
             text.starts_with("# ")
                 || text.starts_with("## ")
                 || text.contains("\n# ")
@@ -517,14 +1159,14 @@ pub mod detectors {
                     .any(|l| l.starts_with("- ") || l.starts_with("* "))
         }
 
-        pub fn is_latex(text: &str) -> bool {
+        pub fn is_latex(text: &Scan) -> bool {
             text.contains("\\documentclass")
                 || text.contains("\\begin{")
                 || text.contains("\\section{")
                 || text.contains("\\usepackage{")
         }
 
-        pub fn is_css(text: &str) -> bool {
+        pub fn is_css(text: &Scan) -> bool {
             text.contains("{")
                 && text.contains("}")
                 && (text.contains("color:")
@@ -533,15 +1175,150 @@ pub mod detectors {
                     || text.contains("padding:"))
         }
 
-        pub fn is_scss(text: &str) -> bool {
+        pub fn is_scss(text: &Scan) -> bool {
             text.contains("$") && text.contains(":") && text.contains(";")
                 || text.contains("@mixin")
                 || text.contains("@include")
+                || text.contains("@extend")
+                // nested rules referring to their parent, `&:hover`
+                || text.lines().any(|l| l.trim_start().starts_with('&'))
+        }
+
+        pub fn is_svg(text: &Scan) -> bool {
+            text.starts_with("<svg") || (text.starts_with("<?xml") && text.contains("<svg"))
+        }
+
+        pub fn is_vue(text: &Scan) -> bool {
+            text.contains("<template>") && (text.contains("<script") || text.contains("<style"))
+        }
+
+        pub fn is_svelte(text: &Scan) -> bool {
+            (text.contains("<script") || text.contains("<style"))
+                && (text.contains("{#if ")
+                    || text.contains("{#each ")
+                    || text.contains("on:click")
+                    || text.contains("bind:")
+                    || text.lines().any(|l| l.trim_start().starts_with("$:")))
+        }
+    }
+
+    /// Explicit type hints: `#!` lines and Vim/Emacs modelines.
+    pub mod hints {
+        use crate::detect::ContentType;
+
+        /// Maps an interpreter name (version suffix already removed) to a type.
+        pub fn interpreter_type(name: &str) -> Option<ContentType> {
+            let content_type = match name {
+                "python" | "pypy" => ContentType::Python,
+                "sh" | "bash" | "zsh" | "ksh" | "dash" | "ash" | "fish" => ContentType::Shell,
+                "node" | "nodejs" | "bun" => ContentType::JavaScript,
+                "deno" | "ts-node" | "tsx" => ContentType::TypeScript,
+                "ruby" => ContentType::Ruby,
+                "perl" => ContentType::Perl,
+                "php" => ContentType::Php,
+                "lua" | "luajit" => ContentType::Lua,
+                "rscript" => ContentType::R,
+                "pwsh" | "powershell" => ContentType::PowerShell,
+                "elixir" => ContentType::Elixir,
+                "scala" => ContentType::Scala,
+                "runhaskell" | "runghc" => ContentType::Haskell,
+                "swift" => ContentType::Swift,
+                "kotlin" => ContentType::Kotlin,
+                "dart" => ContentType::Dart,
+                "make" => ContentType::Makefile,
+                _ => return None,
+            };
+            Some(content_type)
+        }
+
+        /// `python3.11` -> `python`, `perl5` -> `perl`
+        fn strip_version(name: &str) -> &str {
+            name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        }
+
+        /// Resolves the interpreter of a `#!` first line, looking through
+        /// `env` and its options (`#!/usr/bin/env -S node --flag`).
+        pub fn shebang(text: &str) -> Option<ContentType> {
+            let line = text.lines().next()?.strip_prefix("#!")?;
+            let mut words = line.split_whitespace();
+            let basename = |path: &str| path.rsplit('/').next().unwrap_or(path).to_lowercase();
+
+            let mut program = basename(words.next()?);
+            if program == "env" {
+                // skip env's flags and VAR=value assignments
+                let word = words.find(|w| !w.starts_with('-') && !w.contains('='))?;
+                program = basename(word);
+            }
+            interpreter_type(strip_version(&program))
+        }
+
+        fn mode_type(name: &str) -> Option<ContentType> {
+            let name = name.trim().to_lowercase();
+            let name = name.strip_suffix("-mode").unwrap_or(&name);
+            interpreter_type(name).or_else(|| match name {
+                "make" => Some(ContentType::Makefile),
+                "dosini" | "conf" => Some(ContentType::Ini),
+                "js" => Some(ContentType::JavaScript),
+                _ => ContentType::from_name(name),
+            })
+        }
+
+        fn vim_filetype(line: &str) -> Option<&str> {
+            let start = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+                line.match_indices(marker)
+                    .find(|(i, _)| *i == 0 || line[..*i].ends_with(char::is_whitespace))
+                    .map(|(i, _)| i + marker.len())
+            })?;
+            line[start..]
+                .split(|c: char| c.is_whitespace() || c == ':')
+                .find_map(|option| {
+                    ["ft=", "filetype=", "syn=", "syntax="]
+                        .iter()
+                        .find_map(|key| option.strip_prefix(key))
+                })
+        }
+
+        fn emacs_mode(line: &str) -> Option<&str> {
+            let start = line.find("-*-")? + 3;
+            let end = start + line[start..].find("-*-")?;
+            let vars = line[start..end].trim();
+            if !vars.contains(':') {
+                return Some(vars);
+            }
+            vars.split(';').find_map(|var| {
+                let (key, value) = var.split_once(':')?;
+                if key.trim().eq_ignore_ascii_case("mode") {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+        }
+
+        /// Reads a Vim modeline from the first or last five lines, or an
+        /// Emacs `-*- mode -*-` line from the first two.
+        pub fn modeline(text: &str) -> Option<ContentType> {
+            let lines: Vec<&str> = text.lines().collect();
+            let tail = lines.len().saturating_sub(5).max(5);
+            let vim = lines
+                .iter()
+                .take(5)
+                .chain(lines.iter().skip(tail))
+                .find_map(|line| vim_filetype(line));
+            if let Some(content_type) = vim.and_then(mode_type) {
+                return Some(content_type);
+            }
+            lines
+                .iter()
+                .take(2)
+                .find_map(|line| emacs_mode(line).and_then(mode_type))
         }
     }
 
     pub mod special {
-        pub fn is_dockerfile(text: &str) -> bool {
+        use crate::scan::Scan;
+
+        pub fn is_dockerfile(text: &Scan) -> bool {
             text.starts_with("FROM ")
                 || text.contains("\nFROM ")
                 || text.contains("RUN ")
@@ -550,21 +1327,29 @@ pub mod detectors {
                 || text.contains("WORKDIR ")
         }
 
-        pub fn is_gitignore(text: &str) -> bool {
+        pub fn is_gitignore(text: &Scan) -> bool {
             text.lines()
                 .any(|l| l.starts_with("*.") || l.starts_with("/") || l == "node_modules")
         }
 
-        pub fn is_makefile(text: &str) -> bool {
+        pub fn is_makefile(text: &Scan) -> bool {
             text.contains(":\n\t")
                 || text.contains(".PHONY:")
                 || (text
                     .lines()
                     .any(|l| l.ends_with(':') && !l.starts_with('#'))
                     && text.lines().any(|l| l.starts_with('\t')))
+                // `target: prerequisites` followed by a tab-indented recipe
+                || text.lines().zip(text.lines().skip(1)).any(|(rule, recipe)| {
+                    recipe.starts_with('\t')
+                        && !rule.starts_with(['#', '\t'])
+                        && rule.split_once(':').map_or(false, |(target, rest)| {
+                            !target.is_empty() && !target.contains(' ') && !rest.starts_with('=')
+                        })
+                })
         }
 
-        pub fn is_dotenv(text: &str) -> bool {
+        pub fn is_dotenv(text: &Scan) -> bool {
             let has_assignment = text.lines().any(|l| l.contains('=') && !l.starts_with('#'));
             let all_valid = text
                 .lines()
@@ -574,10 +1359,68 @@ pub mod detectors {
             has_assignment && all_valid
         }
 
-        pub fn is_ini(text: &str) -> bool {
-            text.contains("[")
-                && text.contains("]")
-                && text.lines().any(|l| l.contains(" = ") || l.contains("="))
+        pub fn is_ini(text: &Scan) -> bool {
+            // a `[section]` line of its own, not code indexing an array
+            let has_section = text.lines().any(|l| {
+                let l = l.trim();
+                l.starts_with('[') && l.ends_with(']') && !l.starts_with("[[")
+            });
+            has_section && text.lines().any(|l| l.contains(" = ") || l.contains("="))
+        }
+
+        pub fn is_diff(text: &Scan) -> bool {
+            let lines: Vec<&str> = text.lines().collect();
+            let has_headers = lines
+                .windows(2)
+                .any(|w| w[0].starts_with("--- ") && w[1].starts_with("+++ "));
+            let has_hunk = lines.iter().any(|l| l.starts_with("@@ -"));
+
+            // `git format-patch` output starts with a mail header
+            let format_patch = text.starts_with("From ") && text.contains("\ndiff --git ");
+
+            text.starts_with("diff --git ") || format_patch || (has_headers && has_hunk)
+        }
+
+        fn is_log_line(line: &str) -> bool {
+            const LEVELS: [&str; 7] = [
+                "TRACE", "DEBUG", "INFO", "WARN", "WARNING", "ERROR", "FATAL",
+            ];
+            // 2024-01-31 or 2024-01-31T..., optionally in [brackets]
+            let date = line.trim_start_matches('[').as_bytes();
+            let dated = date.len() >= 10
+                && date[..4].iter().all(u8::is_ascii_digit)
+                && date[4] == b'-'
+                && date[5..7].iter().all(u8::is_ascii_digit)
+                && date[7] == b'-';
+            // syslog: `Jun  1 08:00:00`
+            let month = line.get(..4).map_or(false, |m| {
+                m.ends_with(' ') && MONTHS.iter().any(|name| m.starts_with(name))
+            });
+            let clock = line
+                .get(4..)
+                .and_then(|rest| rest.trim_start().split_once(' '))
+                .map_or(false, |(day, rest)| {
+                    day.parse::<u8>().is_ok()
+                        && rest.len() >= 8
+                        && rest.as_bytes()[2] == b':'
+                        && rest.as_bytes()[5] == b':'
+                });
+            let leveled = line
+                .split(|c: char| c.is_whitespace() || c == '[' || c == ']' || c == ':')
+                .filter(|word| !word.is_empty())
+                .take(4)
+                .any(|word| LEVELS.contains(&word));
+            dated || (month && clock) || leveled
+        }
+
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+
+        pub fn is_log(text: &Scan) -> bool {
+            let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+            let log_lines = lines.iter().filter(|l| is_log_line(l)).count();
+            lines.len() >= 2 && log_lines * 2 > lines.len()
         }
     }
 }
@@ -1,7 +1,46 @@
 // Copyright (c) 2025 balintb - https://github.com/balintb/c2f
 // Licensed under the MIT License
 
-use crate::detect::detectors::*;
+use crate::detect::detectors::hints;
+
+// The detectors take the text scanned for keywords; the tests call them
+// through these, with the text itself.
+macro_rules! scanned {
+    ($module:ident: $($detector:ident -> $output:ty),* $(,)?) => {
+        mod $module {
+            use crate::detect::detectors;
+            use crate::scan::Scan;
+            $(
+                pub fn $detector(text: &str) -> $output {
+                    detectors::$module::$detector(&Scan::new(text))
+                }
+            )*
+        }
+    };
+}
+
+scanned!(data:
+    is_json -> bool, is_xml -> bool, is_yaml -> bool, is_toml -> bool, is_csv -> bool,
+    is_tsv -> bool, is_sql -> bool, is_jupyter_notebook -> bool,
+    kubernetes_kind -> Option<String>, is_hcl -> bool, is_protobuf -> bool,
+    is_graphql -> bool,
+);
+scanned!(lang:
+    is_rust -> bool, is_python -> bool, is_typescript -> bool, is_javascript -> bool,
+    is_go -> bool, is_java -> bool, is_csharp -> bool, is_cpp -> bool, is_c -> bool,
+    is_shell -> bool, is_powershell -> bool, is_ruby -> bool, is_php -> bool,
+    is_swift -> bool, is_kotlin -> bool, is_jsx -> bool, is_tsx -> bool, is_nix -> bool,
+    is_lua -> bool, is_perl -> bool, is_r -> bool, is_scala -> bool, is_haskell -> bool,
+    is_elixir -> bool, is_dart -> bool, is_zig -> bool,
+);
+scanned!(markup:
+    is_html -> bool, is_markdown -> bool, is_latex -> bool, is_css -> bool, is_scss -> bool,
+    is_svg -> bool, is_vue -> bool, is_svelte -> bool,
+);
+scanned!(special:
+    is_dockerfile -> bool, is_gitignore -> bool, is_makefile -> bool, is_dotenv -> bool,
+    is_ini -> bool, is_diff -> bool, is_log -> bool,
+);
 
 mod data_tests {
     use super::*;
@@ -12,6 +51,11 @@ mod data_tests {
         assert!(data::is_json(r#"[1, 2, 3]"#));
         assert!(data::is_json(r#"{"nested": {"key": "value"}}"#));
         assert!(!data::is_json("plain text"));
+        // a toml array of tables isn't a json array
+        assert!(!data::is_json(
+            "[[bin]]\nname = \"cli\"\nfeatures = [\"x\"]"
+        ));
+        assert!(data::is_json("[\n  {\"id\": 1}\n]"));
         assert!(!data::is_json("{incomplete"));
     }
 
@@ -21,6 +65,10 @@ mod data_tests {
         assert!(data::is_xml("<root><child></child></root>"));
         assert!(data::is_xml("<tag>content</tag>"));
         assert!(!data::is_xml("plain text"));
+        assert!(!data::is_xml("<table><tr><td>1</td></tr></table>"));
+        assert!(!data::is_xml(
+            "<ul>\n<?php foreach ($a as $b): ?><li></li><?php endforeach; ?>\n</ul>"
+        ));
         assert!(!data::is_xml("<incomplete"));
     }
 
@@ -30,7 +78,12 @@ mod data_tests {
         assert!(data::is_yaml("name: test\nage: 30"));
         assert!(data::is_yaml("items:\n  - one\n  - two"));
         assert!(!data::is_yaml("plain text"));
+        assert!(!data::is_yaml("repositoryOwner(login: $owner) {"));
+        assert!(!data::is_yaml("  error?: string;"));
+        assert!(!data::is_yaml("  theme: {\n    extend: {},\n  },"));
+        assert!(!data::is_yaml("Remember: we meet at 10. Bring the slides."));
         assert!(!data::is_yaml("no colon here"));
+        assert!(!data::is_yaml("Signed by: resident"));
     }
 
     #[test]
@@ -38,6 +91,10 @@ mod data_tests {
         assert!(data::is_toml("[section]\nkey = \"value\""));
         assert!(data::is_toml("[package]\nname = \"test\""));
         assert!(!data::is_toml("plain text"));
+        assert!(!data::is_toml("[flake8]\nprofile = black"));
+        assert!(!data::is_toml("private items: Item[] = [];"));
+        assert!(!data::is_toml("tags = {\n  a = [\"b\"]\n}"));
+        assert!(data::is_toml("[server]\nenabled = true\nports = [80, 443]"));
         assert!(!data::is_toml("key = value")); // missing section
     }
 
@@ -47,6 +104,26 @@ mod data_tests {
         assert!(data::is_csv("a,b,c\n1,2,3"));
         assert!(!data::is_csv("single line"));
         assert!(!data::is_csv("no,commas\nhere"));
+        assert!(data::is_csv("name,note\nJohn,\"hi, there\""));
+        assert!(!data::is_csv("Well, maybe.\nIf so, fine; if not, later."));
+        assert!(!data::is_csv(
+            "It was cold, and late. The streets were\nempty but for a man, and his dog."
+        ));
+    }
+
+    #[test]
+    fn test_tsv_detection() {
+        assert!(data::is_tsv("name\tage\nJohn\t30\nJane\t25"));
+        // commas inside cells don't make it CSV
+        assert!(data::is_tsv("city\tsales\nBerlin\t1,200\nParis\t980"));
+        assert_eq!(
+            crate::detect::detect_text_type("city\tsales\nBerlin\t1,200\nParis\t980"),
+            crate::detect::ContentType::Tsv
+        );
+        // tab-indented code and uneven rows aren't tables
+        assert!(!data::is_tsv("func main() {\n\tfmt.Println(1)\n}"));
+        assert!(!data::is_tsv("a\tb\tc\n1\t2"));
+        assert!(!data::is_tsv("single\tline"));
     }
 
     #[test]
@@ -58,6 +135,76 @@ mod data_tests {
         assert!(data::is_sql("CREATE TABLE test (id INT)"));
         assert!(!data::is_sql("plain text"));
     }
+
+    #[test]
+    fn test_jupyter_notebook_detection() {
+        assert!(data::is_jupyter_notebook(
+            r#"{"cells": [], "metadata": {}, "nbformat": 4, "nbformat_minor": 5}"#
+        ));
+        assert!(!data::is_jupyter_notebook(r#"{"cells": []}"#));
+        assert!(!data::is_jupyter_notebook(r#"{"key": "value"}"#));
+    }
+
+    #[test]
+    fn test_kubernetes_detection() {
+        assert_eq!(
+            data::kubernetes_kind("apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web"),
+            Some("Deployment".to_string())
+        );
+        assert_eq!(
+            data::kubernetes_kind(
+                "apiVersion: v1\nkind: Service\n---\napiVersion: v1\nkind: Service"
+            ),
+            Some("Service".to_string())
+        );
+        assert_eq!(
+            data::kubernetes_kind(
+                "apiVersion: v1\nkind: Service\n---\napiVersion: apps/v1\nkind: Deployment"
+            ),
+            Some("manifests".to_string())
+        );
+        assert_eq!(data::kubernetes_kind("name: test\nkind: nice"), None);
+        assert_eq!(data::kubernetes_kind("plain text"), None);
+    }
+
+    #[test]
+    fn test_hcl_detection() {
+        assert!(data::is_hcl(
+            "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n}"
+        ));
+        assert!(data::is_hcl(
+            "variable \"region\" {\n  default = \"eu-west-1\"\n}"
+        ));
+        assert!(data::is_hcl(
+            "terraform {\n  required_version = \">= 1.0\"\n}"
+        ));
+        assert!(!data::is_hcl("[section]\nkey = \"value\""));
+        assert!(!data::is_hcl("plain text"));
+    }
+
+    #[test]
+    fn test_protobuf_detection() {
+        assert!(data::is_protobuf("syntax = \"proto3\";\npackage api;"));
+        assert!(data::is_protobuf("message User {\n  string name = 1;\n}"));
+        assert!(data::is_protobuf(
+            "service Users {\n  rpc Get (Req) returns (User);\n}"
+        ));
+        assert!(!data::is_protobuf("plain text"));
+    }
+
+    #[test]
+    fn test_graphql_detection() {
+        assert!(data::is_graphql(
+            "query GetUser($id: ID!) {\n  user(id: $id) { name }\n}"
+        ));
+        assert!(data::is_graphql("mutation {\n  logout\n}"));
+        assert!(data::is_graphql(
+            "type User {\n  id: ID!\n  posts: [Post]\n}"
+        ));
+        assert!(data::is_graphql("schema {\n  query: Query\n}"));
+        assert!(!data::is_graphql("type ID = number;"));
+        assert!(!data::is_graphql("name: test\nage: 30"));
+    }
 }
 
 mod lang_tests {
@@ -82,6 +229,10 @@ mod lang_tests {
         assert!(lang::is_python("from datetime import datetime"));
         assert!(lang::is_python("class MyClass:"));
         assert!(!lang::is_python("plain text"));
+        assert!(!lang::is_python("Departure 14:35 from Gate B12"));
+        assert!(!lang::is_python("import { Router } from 'express';"));
+        assert!(!lang::is_python("public static class Util\n{\n}"));
+        assert!(!lang::is_python("print('hi');"));
     }
 
     #[test]
@@ -93,6 +244,9 @@ mod lang_tests {
         assert!(lang::is_javascript("let arr = [1, 2, 3]"));
         assert!(lang::is_javascript("var obj = {}"));
         assert!(!lang::is_javascript("plain text"));
+        assert!(lang::is_javascript("module.exports = { plugins: [] };"));
+        // type annotations make it typescript
+        assert!(!lang::is_javascript("const name: string = 'x';"));
     }
 
     #[test]
@@ -105,6 +259,10 @@ mod lang_tests {
             "function test(): string { return ''; }"
         ));
         assert!(!lang::is_typescript("plain text"));
+        assert!(lang::is_typescript("export type Status = 'idle' | 'done';"));
+        assert!(!lang::is_typescript(
+            "variable \"env\" {\n  type = string\n}"
+        ));
     }
 
     #[test]
@@ -123,6 +281,8 @@ mod lang_tests {
         assert!(lang::is_java("public static void main(String[] args) {}"));
         assert!(lang::is_java("private int count;"));
         assert!(!lang::is_java("plain text"));
+        assert!(lang::is_java("} catch (IOException e) {\n    log(e);\n}"));
+        assert!(!lang::is_java("} catch (err) {\n  next(err);\n}"));
     }
 
     #[test]
@@ -159,6 +319,7 @@ mod lang_tests {
         assert!(lang::is_shell("if [ -f file ]; then echo exists; fi"));
         assert!(lang::is_shell("echo 'test'"));
         assert!(!lang::is_shell("plain text"));
+        assert!(!lang::is_shell("Thanks for the update!"));
     }
 
     #[test]
@@ -179,6 +340,9 @@ mod lang_tests {
         assert!(lang::is_ruby("def method\nend"));
         assert!(lang::is_ruby("class MyClass\nend"));
         assert!(!lang::is_ruby("plain text"));
+        assert!(lang::is_ruby(
+            "describe Cart do\n  it \"works\" do\n  end\nend"
+        ));
     }
 
     #[test]
@@ -205,6 +369,144 @@ mod lang_tests {
         assert!(lang::is_kotlin("var count: Int = 0"));
         assert!(lang::is_kotlin("import kotlin.math.*"));
         assert!(!lang::is_kotlin("plain text"));
+        assert!(!lang::is_kotlin(
+            "val xs = List(1, 2)\ndef twice(x: Int) = x * 2"
+        ));
+    }
+
+    #[test]
+    fn test_jsx_detection() {
+        assert!(lang::is_jsx(
+            "export default function App() {\n  return (\n    <div className=\"app\" />\n  );\n}"
+        ));
+        assert!(lang::is_jsx(
+            "import { useState } from 'react';\nconst C = () => (<p>hi</p>);"
+        ));
+        assert!(!lang::is_jsx("const x = 1;"));
+        assert!(!lang::is_jsx("<div className=\"x\"></div>"));
+        // markup on the line after an open paren, with expression attributes
+        assert!(lang::is_jsx(
+            "const List = ({ items }) =>\n  (\n    <ul>{items.map((i) => <li key={i.id} />)}</ul>\n  );"
+        ));
+        assert!(lang::is_jsx(
+            "root.render(\n  <React.StrictMode>\n    <App />\n  </React.StrictMode>\n);"
+        ));
+    }
+
+    #[test]
+    fn test_tsx_detection() {
+        assert!(lang::is_tsx(
+            "interface Props { name: string }\nexport const Hi = ({ name }: Props) => (<p className=\"hi\">{name}</p>);"
+        ));
+        assert!(!lang::is_tsx(
+            "export default function App() {\n  return (<div className=\"app\" />);\n}"
+        ));
+    }
+
+    #[test]
+    fn test_nix_detection() {
+        assert!(lang::is_nix(
+            "{ pkgs ? import <nixpkgs> {} }:\npkgs.mkShell { }"
+        ));
+        assert!(lang::is_nix(
+            "stdenv.mkDerivation {\n  pname = \"hello\";\n}"
+        ));
+        assert!(lang::is_nix(
+            "{ config, lib, ... }:\n{\n  services.nginx.enable = true;\n}"
+        ));
+        assert!(!lang::is_nix("plain text"));
+    }
+
+    #[test]
+    fn test_lua_detection() {
+        assert!(lang::is_lua(
+            "local function greet(name)\n  print(name)\nend"
+        ));
+        assert!(lang::is_lua(
+            "local count = 0\nfor i = 1, 10 do\n  count = count + i\nend"
+        ));
+        assert!(lang::is_lua(
+            "if x > 1 then\n  y()\nelseif x < 0 then\n  z()\nend"
+        ));
+        assert!(!lang::is_lua("plain text"));
+    }
+
+    #[test]
+    fn test_perl_detection() {
+        assert!(lang::is_perl("#!/usr/bin/perl\nprint \"hi\\n\";"));
+        assert!(lang::is_perl("use strict;\nuse warnings;"));
+        assert!(lang::is_perl("my $name = shift;"));
+        assert!(!lang::is_perl("plain text"));
+    }
+
+    #[test]
+    fn test_r_detection() {
+        assert!(lang::is_r("library(ggplot2)"));
+        assert!(lang::is_r("square <- function(x) x^2"));
+        assert!(lang::is_r("values <- c(1, 2, 3)"));
+        assert!(!lang::is_r("ch <- value"));
+        assert!(!lang::is_r("plain text"));
+        assert!(lang::is_r("fit <- lm(mpg ~ wt, data = mtcars)"));
+    }
+
+    #[test]
+    fn test_scala_detection() {
+        assert!(lang::is_scala("case class User(name: String)"));
+        assert!(lang::is_scala(
+            "object Main extends App {\n  println(\"hi\")\n}"
+        ));
+        assert!(lang::is_scala("import scala.collection.mutable"));
+        assert!(lang::is_scala("sealed trait Shape"));
+        assert!(!lang::is_scala("plain text"));
+        assert!(lang::is_scala("def fib(n: Int): BigInt = {\n  ???\n}"));
+        assert!(lang::is_scala("import spark.implicits._"));
+        assert!(lang::is_scala(
+            "implicit val ord: Ordering[Int] = Ordering.Int"
+        ));
+        assert!(!lang::is_scala("def area(self) -> float:\n    return 0"));
+    }
+
+    #[test]
+    fn test_haskell_detection() {
+        assert!(lang::is_haskell("module Main where\n\nmain :: IO ()"));
+        assert!(lang::is_haskell("import qualified Data.Map as Map"));
+        assert!(lang::is_haskell(
+            "add :: Int -> Int -> Int\nadd x y = x + y"
+        ));
+        assert!(lang::is_haskell("main = do\n  putStrLn \"hi\""));
+        assert!(!lang::is_haskell("std::vector<int> v;"));
+        assert!(!lang::is_haskell("plain text"));
+    }
+
+    #[test]
+    fn test_elixir_detection() {
+        assert!(lang::is_elixir(
+            "defmodule Greeter do\n  def hi, do: :ok\nend"
+        ));
+        assert!(lang::is_elixir(
+            "list\n|> Enum.map(&(&1 * 2))\n|> Enum.sum()\nend"
+        ));
+        assert!(!lang::is_elixir("def greet():\n    pass"));
+        assert!(!lang::is_elixir("plain text"));
+    }
+
+    #[test]
+    fn test_dart_detection() {
+        assert!(lang::is_dart("import 'package:flutter/material.dart';"));
+        assert!(lang::is_dart("import 'dart:async';"));
+        assert!(lang::is_dart(
+            "@override\nWidget build(BuildContext context) {}"
+        ));
+        assert!(!lang::is_dart("plain text"));
+    }
+
+    #[test]
+    fn test_zig_detection() {
+        assert!(lang::is_zig("const std = @import(\"std\");"));
+        assert!(lang::is_zig("pub fn main() !void {}"));
+        assert!(lang::is_zig("fn max(comptime T: type, a: T, b: T) T {}"));
+        assert!(!lang::is_zig("pub fn main() {}"));
+        assert!(!lang::is_zig("plain text"));
     }
 }
 
@@ -218,6 +520,7 @@ mod markup_tests {
         assert!(markup::is_html("<html><body><p>Text</p></body></html>"));
         assert!(markup::is_html("<span>test</span>"));
         assert!(!markup::is_html("plain text"));
+        assert!(markup::is_html("<table>\n  <tr><td>1</td></tr>\n</table>"));
     }
 
     #[test]
@@ -255,6 +558,43 @@ mod markup_tests {
         assert!(markup::is_scss("@mixin button { }"));
         assert!(markup::is_scss("@include mixin;"));
         assert!(!markup::is_scss("plain text"));
+        assert!(markup::is_scss(
+            ".nav {\n  a {\n    &:hover { color: red; }\n  }\n}"
+        ));
+        assert!(markup::is_scss(".error {\n  @extend %message;\n}"));
+    }
+
+    #[test]
+    fn test_svg_detection() {
+        assert!(markup::is_svg(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"
+        ));
+        assert!(markup::is_svg(
+            "<?xml version=\"1.0\"?>\n<svg width=\"10\"></svg>"
+        ));
+        assert!(!markup::is_svg("<?xml version=\"1.0\"?>\n<root/>"));
+        assert!(!markup::is_svg("plain text"));
+    }
+
+    #[test]
+    fn test_vue_detection() {
+        assert!(markup::is_vue(
+            "<template>\n  <p>{{ msg }}</p>\n</template>\n<script setup>\nconst msg = 'hi'\n</script>"
+        ));
+        assert!(!markup::is_vue("<template><p>hi</p></template>"));
+        assert!(!markup::is_vue("plain text"));
+    }
+
+    #[test]
+    fn test_svelte_detection() {
+        assert!(markup::is_svelte(
+            "<script>\n  let count = 0;\n</script>\n<button on:click={() => count++}>{count}</button>"
+        ));
+        assert!(markup::is_svelte(
+            "<script>\n  export let items;\n</script>\n{#each items as item}\n  <li>{item}</li>\n{/each}"
+        ));
+        assert!(!markup::is_svelte("<script>alert(1)</script>"));
+        assert!(!markup::is_svelte("plain text"));
     }
 }
 
@@ -285,6 +625,9 @@ mod special_tests {
         assert!(special::is_makefile(".PHONY: clean"));
         assert!(!special::is_makefile("plain text"));
         assert!(!special::is_makefile("# Markdown Title"));
+        assert!(special::is_makefile(
+            "prog: $(objects)\n\t$(CC) -o prog $(objects)"
+        ));
     }
 
     #[test]
@@ -301,6 +644,172 @@ mod special_tests {
         assert!(special::is_ini("[section]\nkey = value"));
         assert!(special::is_ini("[database]\nhost=localhost"));
         assert!(!special::is_ini("plain text"));
+        assert!(!special::is_ini("let first = xs[0];\nconst ys = [1];"));
+    }
+
+    #[test]
+    fn test_diff_detection() {
+        assert!(special::is_diff(
+            "diff --git a/src/main.rs b/src/main.rs\nindex 1..2 100644"
+        ));
+        assert!(special::is_diff(
+            "--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n-old\n+new"
+        ));
+        assert!(special::is_diff(
+            "From 1a2b3c Mon Sep 17 00:00:00 2001\nSubject: [PATCH] fix\n\ndiff --git a/x b/x"
+        ));
+        assert!(!special::is_diff("--- \ntitle: yaml front matter\n---"));
+        assert!(!special::is_diff("plain text"));
+    }
+
+    #[test]
+    fn test_log_detection() {
+        assert!(special::is_log(
+            "2024-05-01 12:00:01 INFO server started\n2024-05-01 12:00:02 WARN slow request"
+        ));
+        assert!(special::is_log(
+            "[2024-05-01T12:00:01Z] starting\n[2024-05-01T12:00:02Z] ready"
+        ));
+        assert!(special::is_log(
+            "INFO: starting worker\nERROR: connection refused\nINFO: retrying"
+        ));
+        assert!(!special::is_log("2024-05-01 12:00:01 single line"));
+        assert!(!special::is_log("name: test\nage: 30"));
+        assert!(special::is_log(
+            "Jun  1 08:00:00 host kernel: started\nJun  1 08:00:01 host systemd[1]: ready"
+        ));
+    }
+}
+
+mod hints_tests {
+    use super::*;
+    use crate::detect::ContentType;
+
+    #[test]
+    fn test_shebang_interpreters() {
+        assert_eq!(
+            hints::shebang("#!/usr/bin/python3.11"),
+            Some(ContentType::Python)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env python3"),
+            Some(ContentType::Python)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env -S node --no-warnings"),
+            Some(ContentType::JavaScript)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env -S deno run"),
+            Some(ContentType::TypeScript)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env LANG=C perl -w"),
+            Some(ContentType::Perl)
+        );
+        assert_eq!(hints::shebang("#! /bin/bash -e"), Some(ContentType::Shell));
+        assert_eq!(
+            hints::shebang("#!/usr/local/bin/zsh"),
+            Some(ContentType::Shell)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/ruby2.7"),
+            Some(ContentType::Ruby)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env Rscript"),
+            Some(ContentType::R)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/env pwsh"),
+            Some(ContentType::PowerShell)
+        );
+        assert_eq!(
+            hints::shebang("#!/usr/bin/make -f"),
+            Some(ContentType::Makefile)
+        );
+        assert_eq!(hints::shebang("#!/usr/bin/env unknown-tool"), None);
+        assert_eq!(hints::shebang("#!/usr/bin/env"), None);
+        assert_eq!(hints::shebang("# comment\n#!/bin/sh"), None);
+    }
+
+    #[test]
+    fn test_vim_modelines() {
+        assert_eq!(
+            hints::modeline("# vim: ft=python"),
+            Some(ContentType::Python)
+        );
+        assert_eq!(
+            hints::modeline("line\n\n/* vim: set filetype=javascript : */"),
+            Some(ContentType::JavaScript)
+        );
+        assert_eq!(
+            hints::modeline("x = 1\n# vi: set ts=4 sw=4 syntax=yaml:"),
+            Some(ContentType::Yaml)
+        );
+        assert_eq!(
+            hints::modeline("# vim: ft=make"),
+            Some(ContentType::Makefile)
+        );
+        assert_eq!(hints::modeline("# vim: ts=4 sw=4"), None);
+        assert_eq!(hints::modeline("nvim:ft=python"), None);
+    }
+
+    #[test]
+    fn test_modeline_position() {
+        let body = "a\n".repeat(20);
+        assert_eq!(
+            hints::modeline(&format!("{body}# vim: ft=sh")),
+            Some(ContentType::Shell)
+        );
+        // only the first and last five lines count
+        assert_eq!(
+            hints::modeline(&format!("{body}# vim: ft=sh\n{body}")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_emacs_modelines() {
+        assert_eq!(
+            hints::modeline("# -*- mode: ruby; coding: utf-8 -*-"),
+            Some(ContentType::Ruby)
+        );
+        assert_eq!(
+            hints::modeline(";; -*- python -*-"),
+            Some(ContentType::Python)
+        );
+        assert_eq!(
+            hints::modeline("#!/bin/false\n# -*- mode: shell-script -*-"),
+            Some(ContentType::Shell)
+        );
+        assert_eq!(hints::modeline("// -*- c++ -*-"), Some(ContentType::Cpp));
+        assert_eq!(hints::modeline("# -*- coding: utf-8 -*-"), None);
+        assert_eq!(hints::modeline("a\nb\n# -*- python -*-"), None);
+    }
+
+    #[test]
+    fn test_hints_beat_heuristics() {
+        use crate::detect::detect_text_type;
+
+        // reads like yaml and shell, but the shebang says node
+        assert_eq!(
+            detect_text_type("#!/usr/bin/env -S node\nconsole.log('key: value')\necho"),
+            ContentType::JavaScript
+        );
+        assert_eq!(
+            detect_text_type("#!/usr/bin/python3.11\nname: str = 'x'"),
+            ContentType::Python
+        );
+        assert_eq!(
+            detect_text_type("name: test\n# vim: ft=ruby"),
+            ContentType::Ruby
+        );
+        // so does php's opening tag
+        assert_eq!(
+            detect_text_type("<?php\nfunction f(string $s): string { return $s; }"),
+            ContentType::Php
+        );
     }
 }
 
@@ -323,6 +832,26 @@ fn test_detect_text_type_priority() {
     );
 }
 
+#[test]
+fn test_single_rule_match_stands() {
+    use crate::detect::{detect_text_type, ContentType};
+
+    // only one rule matches, so the classifier isn't consulted
+    assert_eq!(detect_text_type("retries: 3"), ContentType::Yaml);
+}
+
+#[test]
+fn test_is_code() {
+    use crate::detect::ContentType;
+
+    assert!(ContentType::Python.is_code());
+    assert!(ContentType::Yaml.is_code());
+    assert!(ContentType::Dockerfile.is_code());
+    assert!(!ContentType::Markdown.is_code());
+    assert!(!ContentType::Csv.is_code());
+    assert!(!ContentType::PlainText.is_code());
+}
+
 #[test]
 fn test_real_world_samples() {
     use crate::detect::{detect_text_type, ContentType};
@@ -437,3 +966,477 @@ fn test_edge_cases() {
         ContentType::DotEnv
     );
 }
+
+#[test]
+fn test_content_type_from_name() {
+    use crate::detect::ContentType;
+
+    assert_eq!(ContentType::from_name("yaml"), Some(ContentType::Yaml));
+    assert_eq!(ContentType::from_name("YAML"), Some(ContentType::Yaml));
+    assert_eq!(ContentType::from_name("yml"), Some(ContentType::Yaml));
+    assert_eq!(ContentType::from_name("py"), Some(ContentType::Python));
+    assert_eq!(ContentType::from_name("python"), Some(ContentType::Python));
+    assert_eq!(ContentType::from_name("C#"), Some(ContentType::CSharp));
+    assert_eq!(ContentType::from_name("c++"), Some(ContentType::Cpp));
+    assert_eq!(ContentType::from_name("c"), Some(ContentType::C));
+    assert_eq!(
+        ContentType::from_name("shell-script"),
+        Some(ContentType::Shell)
+    );
+    assert_eq!(ContentType::from_name("env"), Some(ContentType::DotEnv));
+    assert_eq!(ContentType::from_name("txt"), Some(ContentType::PlainText));
+    assert_eq!(ContentType::from_name("cobol"), None);
+    assert_eq!(ContentType::from_name(""), None);
+
+    // every type round-trips through its display name, and its extension
+    // resolves to a type saved the same way (kubernetes manifests are yaml)
+    for content_type in ContentType::ALL {
+        assert_eq!(
+            ContentType::from_name(content_type.extension()).map(|t| t.extension().to_string()),
+            Some(content_type.extension().to_string())
+        );
+        assert_eq!(
+            ContentType::from_name(&content_type.to_string()).as_ref(),
+            Some(content_type)
+        );
+    }
+}
+
+#[test]
+fn test_write_png_round_trip() {
+    use crate::detect::{png_to_image, write_png, PNG_SIGNATURE};
+    use arboard::ImageData;
+
+    let (width, height) = (3, 250);
+    let pixels: Vec<u8> = (0..width * height * 4).map(|i| (i % 251) as u8).collect();
+    let img = ImageData {
+        width,
+        height,
+        bytes: pixels.clone().into(),
+    };
+
+    let mut png = Vec::new();
+    let mut reported = Vec::new();
+    write_png(&img, &mut png, &mut |done, total| {
+        reported.push((done, total))
+    })
+    .unwrap();
+    assert!(png.starts_with(PNG_SIGNATURE));
+    assert_eq!(reported.last(), Some(&(height, height)));
+    assert!(reported.windows(2).all(|w| w[0].0 < w[1].0));
+
+    let decoded = png_to_image(&png).unwrap();
+    assert_eq!((decoded.width, decoded.height), (width, height));
+    assert_eq!(decoded.bytes.as_ref(), &pixels[..]);
+
+    // pixel data that doesn't match the size is refused
+    let short = ImageData {
+        width,
+        height,
+        bytes: pixels[1..].to_vec().into(),
+    };
+    assert!(write_png(&short, &mut Vec::new(), &mut |_, _| {}).is_err());
+}
+
+#[test]
+fn test_detect_bytes() {
+    use crate::detect::{detect_bytes, ContentType, DetectOptions, PNG_SIGNATURE};
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    png.extend_from_slice(b"\0\0\0\rIHDR");
+    assert_eq!(
+        detect_bytes(&png, &DetectOptions::default()),
+        ContentType::Image
+    );
+    assert_eq!(
+        detect_bytes(br#"{"key": "value"}"#, &DetectOptions::default()),
+        ContentType::Json
+    );
+    assert_eq!(
+        detect_bytes(b"\xff\xfe plain \xff", &DetectOptions::default()),
+        ContentType::PlainText
+    );
+}
+
+#[test]
+fn test_large_inputs_are_sampled() {
+    use crate::detect::{detect_bytes, detect_text_type_with, ContentType, DetectOptions};
+
+    let options = DetectOptions {
+        max_bytes: 4096,
+        ..Default::default()
+    };
+    // a few megabytes of csv, json and yaml still read as such
+    let csv: String = std::iter::once("id,name,email\n".to_string())
+        .chain((0..100_000).map(|i| format!("{i},user{i},user{i}@example.com\n")))
+        .collect();
+    assert_eq!(detect_text_type_with(&csv, &options), ContentType::Csv);
+    assert_eq!(detect_bytes(csv.as_bytes(), &options), ContentType::Csv);
+
+    let items: Vec<String> = (0..100_000).map(|i| format!("{{\"id\": {i}}}")).collect();
+    let json = format!("[\n{}\n]", items.join(",\n"));
+    assert_eq!(detect_text_type_with(&json, &options), ContentType::Json);
+
+    // the middle is skipped: a marker only found there doesn't count
+    let middle = format!(
+        "{}--- a/x\n+++ b/x\n@@ -1 +1 @@\n{}",
+        "word\n".repeat(10_000),
+        "word\n".repeat(10_000)
+    );
+    assert_eq!(
+        detect_text_type_with(&middle, &options),
+        ContentType::PlainText
+    );
+    let everything = DetectOptions {
+        max_bytes: 0,
+        ..Default::default()
+    };
+    assert_eq!(
+        detect_text_type_with(&middle, &everything),
+        ContentType::Diff
+    );
+}
+
+#[test]
+fn test_new_formats_end_to_end() {
+    use crate::detect::{detect_text_type, ContentType};
+
+    let cases = [
+        (
+            "diff --git a/app.py b/app.py\n--- a/app.py\n+++ b/app.py\n@@ -1 +1 @@\n-import os\n+import sys",
+            ContentType::Diff,
+        ),
+        (
+            r#"{"cells": [{"cell_type": "code", "source": ["print(1)"]}], "nbformat": 4, "nbformat_minor": 5}"#,
+            ContentType::JupyterNotebook,
+        ),
+        (
+            "<svg viewBox=\"0 0 10 10\"><circle r=\"4\"/></svg>",
+            ContentType::Svg,
+        ),
+        (
+            "<template>\n  <div>{{ title }}</div>\n</template>\n\n<script>\nexport default { data() { return { title: 'hi' } } }\n</script>",
+            ContentType::Vue,
+        ),
+        (
+            "<script>\n  let name = 'world';\n  $: greeting = `Hello ${name}`;\n</script>\n\n<h1>{greeting}</h1>",
+            ContentType::Svelte,
+        ),
+        (
+            "import React from 'react';\n\nexport function Button({ label }) {\n  return (\n    <button className=\"btn\">{label}</button>\n  );\n}",
+            ContentType::Jsx,
+        ),
+        (
+            "import React from 'react';\n\ninterface Props { label: string }\n\nexport function Button({ label }: Props) {\n  return <button className=\"btn\">{label}</button>;\n}",
+            ContentType::Tsx,
+        ),
+        (
+            "2024-05-01 12:00:01 INFO  api: listening on :8080\n2024-05-01 12:00:05 ERROR api: upstream timeout\n2024-05-01 12:00:06 INFO  api: retrying",
+            ContentType::Log,
+        ),
+        (
+            "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web\nspec:\n  replicas: 2",
+            ContentType::Kubernetes {
+                kind: "Deployment".to_string(),
+            },
+        ),
+        (
+            "provider \"aws\" {\n  region = \"eu-west-1\"\n}\n\nresource \"aws_instance\" \"web\" {\n  ami = \"ami-123\"\n  tags = [\"web\"]\n}",
+            ContentType::Hcl,
+        ),
+        (
+            "syntax = \"proto3\";\n\nmessage SearchRequest {\n  string query = 1;\n  int32 page = 2;\n}",
+            ContentType::Protobuf,
+        ),
+        (
+            "type Query {\n  user(id: ID!): User\n}\n\ntype User {\n  id: ID!\n  name: String!\n}",
+            ContentType::GraphQl,
+        ),
+        (
+            "{ pkgs ? import <nixpkgs> {} }:\n\npkgs.mkShell {\n  buildInputs = [ pkgs.cargo ];\n}",
+            ContentType::Nix,
+        ),
+        (
+            "local M = {}\n\nfunction M.greet(name)\n  print(\"Hello \" .. name)\nend\n\nreturn M",
+            ContentType::Lua,
+        ),
+        (
+            "#!/usr/bin/perl\nuse strict;\nuse warnings;\n\nmy @names = ('a', 'b');\nforeach my $n (@names) { print \"$n\\n\"; }",
+            ContentType::Perl,
+        ),
+        (
+            "library(dplyr)\n\ndf <- data.frame(x = c(1, 2, 3))\nsummary(df)",
+            ContentType::R,
+        ),
+        (
+            "object Main extends App {\n  case class Point(x: Int, y: Int)\n  println(Point(1, 2))\n}",
+            ContentType::Scala,
+        ),
+        (
+            "module Main where\n\nimport Data.List (sort)\n\nmain :: IO ()\nmain = print (sort [3, 1, 2])",
+            ContentType::Haskell,
+        ),
+        (
+            "defmodule Math do\n  def sum(a, b) do\n    a + b\n  end\nend",
+            ContentType::Elixir,
+        ),
+        (
+            "import 'package:flutter/material.dart';\n\nvoid main() => runApp(const MyApp());",
+            ContentType::Dart,
+        ),
+        (
+            "const std = @import(\"std\");\n\npub fn main() !void {\n    std.debug.print(\"hi\\n\", .{});\n}",
+            ContentType::Zig,
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(detect_text_type(text), expected, "{text}");
+    }
+}
+
+#[test]
+fn test_kubernetes_file_stem() {
+    use crate::detect::{detect_text_type, ContentType};
+
+    let manifest = detect_text_type("apiVersion: v1\nkind: ConfigMap\ndata:\n  key: value");
+    assert_eq!(manifest.extension(), "yaml");
+    assert_eq!(manifest.file_stem(), Some("configmap".to_string()));
+    assert_eq!(manifest.to_string(), "Kubernetes ConfigMap");
+    assert_eq!(ContentType::Yaml.file_stem(), None);
+}
+
+#[test]
+fn test_kubernetes_kind_cannot_escape_directory() {
+    use crate::detect::{detect_text_type, ContentType};
+
+    let traversal = detect_text_type("apiVersion: v1\nkind: ../../x\nmetadata:\n  name: a");
+    assert_eq!(traversal.file_stem(), Some("x".to_string()));
+    let absolute = detect_text_type("apiVersion: v1\nkind: /tmp/x\nmetadata:\n  name: a");
+    assert_eq!(absolute.file_stem(), Some("tmpx".to_string()));
+
+    // nothing usable left, so it isn't taken as a kind at all
+    let empty = detect_text_type("apiVersion: v1\nkind: ../..\nmetadata:\n  name: a");
+    assert_eq!(empty.file_stem(), None);
+
+    let built = ContentType::Kubernetes {
+        kind: "../Secret".to_string(),
+    };
+    assert_eq!(built.file_stem(), Some("secret".to_string()));
+}
+
+mod corpus_tests {
+    use crate::classifier::classify;
+    use crate::detect::{detect_bytes, detect_text_type_with, ContentType, DetectOptions};
+    use std::collections::{BTreeMap, HashSet};
+    use std::mem::discriminant;
+    use std::path::Path;
+    use std::time::Instant;
+
+    struct Sample {
+        path: String,
+        expected: ContentType,
+        bytes: Vec<u8>,
+    }
+
+    /// Labelled samples in tests/fixtures/detect/<type>/, where <type> is
+    /// anything `ContentType::from_name` accepts. None of them repeat the
+    /// classifier's training data (see `test_corpus_is_not_training_data`).
+    fn corpus() -> Vec<Sample> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/detect");
+        let mut samples = Vec::new();
+        for dir in std::fs::read_dir(&root).unwrap() {
+            let dir = dir.unwrap().path();
+            let label = dir.file_name().unwrap().to_str().unwrap();
+            let expected = ContentType::from_name(label)
+                .unwrap_or_else(|| panic!("fixture directory '{label}' is not a type"));
+            for file in std::fs::read_dir(&dir).unwrap() {
+                let file = file.unwrap().path();
+                samples.push(Sample {
+                    path: file.strip_prefix(&root).unwrap().display().to_string(),
+                    expected: expected.clone(),
+                    bytes: std::fs::read(&file).unwrap(),
+                });
+            }
+        }
+        samples.sort_by(|a, b| {
+            (a.expected.extension(), &a.path).cmp(&(b.expected.extension(), &b.path))
+        });
+        samples
+    }
+
+    // a Kubernetes manifest is right whatever its kind
+    fn same_type(a: &ContentType, b: &ContentType) -> bool {
+        match (a, b) {
+            (ContentType::Kubernetes { .. }, ContentType::Kubernetes { .. }) => true,
+            _ => a == b,
+        }
+    }
+
+    /// Every run of three non-blank lines, trimmed, that is long enough to
+    /// be more than boilerplate like `}` or `end`.
+    fn line_runs(text: &str) -> HashSet<String> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        lines
+            .windows(3)
+            .filter(|run| run.iter().map(|l| l.len()).sum::<usize>() >= 40)
+            .map(|run| run.join("\n"))
+            .collect()
+    }
+
+    #[test]
+    fn test_corpus_is_not_training_data() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/classifier");
+        let mut training = HashSet::new();
+        for file in std::fs::read_dir(&root).unwrap() {
+            let text = std::fs::read_to_string(file.unwrap().path())
+                .unwrap()
+                .replace("\r\n", "\n");
+            for sample in text.split("\n<<<>>>\n") {
+                training.extend(line_runs(sample));
+            }
+        }
+
+        for sample in corpus() {
+            let runs = line_runs(&String::from_utf8_lossy(&sample.bytes));
+            let shared: Vec<&String> = runs.intersection(&training).collect();
+            assert!(
+                shared.is_empty(),
+                "{} repeats training data: {shared:?}",
+                sample.path
+            );
+        }
+    }
+
+    #[test]
+    fn test_corpus_covers_every_type() {
+        let samples = corpus();
+        for content_type in ContentType::ALL {
+            assert!(
+                samples
+                    .iter()
+                    .any(|s| discriminant(&s.expected) == discriminant(content_type)),
+                "no fixtures for {content_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_confusion_matrix() {
+        let samples = corpus();
+        // expected -> detected -> count, by display name
+        let mut matrix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        let mut correct = 0;
+        let mut classifier_correct = 0;
+        for sample in &samples {
+            let detected = detect_bytes(&sample.bytes, &DetectOptions::default());
+            if same_type(&detected, &sample.expected) {
+                correct += 1;
+            } else {
+                println!(
+                    "{}: expected {}, detected {detected}",
+                    sample.path, sample.expected
+                );
+            }
+            let row = matrix.entry(sample.expected.to_string()).or_default();
+            let column = if same_type(&detected, &sample.expected) {
+                sample.expected.to_string()
+            } else {
+                detected.to_string()
+            };
+            *row.entry(column).or_default() += 1;
+
+            let text = String::from_utf8_lossy(&sample.bytes);
+            let prediction = classify(&text, &[]).map(|p| p.content_type);
+            if prediction.map_or(false, |p| same_type(&p, &sample.expected)) {
+                classifier_correct += 1;
+            }
+        }
+
+        println!("\n{:<24} {:>7}  confused with", "expected", "correct");
+        let mut worst_recall: f64 = 1.0;
+        for (expected, row) in &matrix {
+            let total: usize = row.values().sum();
+            let hits = row.get(expected).copied().unwrap_or(0);
+            let confusions: Vec<String> = row
+                .iter()
+                .filter(|(detected, _)| *detected != expected)
+                .map(|(detected, count)| format!("{detected} ({count})"))
+                .collect();
+            println!(
+                "{expected:<24} {:>7}  {}",
+                format!("{hits}/{total}"),
+                confusions.join(", ")
+            );
+            worst_recall = worst_recall.min(hits as f64 / total as f64);
+        }
+
+        let accuracy = correct as f64 / samples.len() as f64;
+        println!(
+            "\ndetection accuracy: {correct}/{} ({:.1}%), classifier alone: {classifier_correct}/{}",
+            samples.len(),
+            accuracy * 100.0,
+            samples.len()
+        );
+        assert!(accuracy >= 0.95, "accuracy dropped to {accuracy:.3}");
+        assert!(
+            worst_recall >= 0.8,
+            "a type is detected only {:.0}% of the time",
+            worst_recall * 100.0
+        );
+    }
+
+    /// Throughput on large inputs. Run with
+    /// `cargo test --release bench_ -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_detect_throughput() {
+        let log_line = "2024-06-01 08:00:00,123 INFO  [main] request completed status=200\n";
+        let csv_row = "1001,2024-02-01 09:12:00,ACME Corp,1200.00,EUR\n";
+        let code = include_str!("detect.rs");
+        let inputs = [
+            ("log", log_line.repeat(8 * 1024 * 1024 / log_line.len())),
+            ("csv", csv_row.repeat(8 * 1024 * 1024 / csv_row.len())),
+            ("rust", code.repeat(8 * 1024 * 1024 / code.len())),
+            (
+                "prose",
+                "All work and no play makes Jack a dull boy. ".repeat(200_000),
+            ),
+        ];
+        // the default sampling window, and the whole text
+        let sampled = DetectOptions::default();
+        let full = DetectOptions {
+            max_bytes: 0,
+            ..Default::default()
+        };
+        for (name, text) in &inputs {
+            for (mode, options) in [("sampled", &sampled), ("full", &full)] {
+                let runs = 5;
+                let start = Instant::now();
+                for _ in 0..runs {
+                    detect_text_type_with(text, options);
+                }
+                let elapsed = start.elapsed() / runs;
+                let mb = text.len() as f64 / (1024.0 * 1024.0);
+                println!(
+                    "{name:<6} {mode:<8} {mb:>6.1} MiB  {elapsed:>12.2?}  {:>8.1} MiB/s",
+                    mb / elapsed.as_secs_f64()
+                );
+            }
+            // the tokenizer caps its input, so this is the classifier's worst case
+            let runs = 20;
+            let start = Instant::now();
+            for _ in 0..runs {
+                classify(text, &[]);
+            }
+            println!(
+                "{name:<6} {:<8} {:>22.2?}",
+                "classify",
+                start.elapsed() / runs
+            );
+        }
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType;
+use c2f::Config;
+use std::io::{self, Write};
+
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Eol {
+    Lf,
+    Crlf,
+    Native,
+    Keep,
+}
+
+impl Eol {
+    pub const VALUES: &'static [&'static str] = &["lf", "crlf", "native", "keep"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "lf" => Some(Eol::Lf),
+            "crlf" => Some(Eol::Crlf),
+            "native" => Some(Eol::Native),
+            "keep" => Some(Eol::Keep),
+            _ => None,
+        }
+    }
+
+    fn separator(self) -> Option<&'static str> {
+        match self {
+            Eol::Lf => Some("\n"),
+            Eol::Crlf => Some("\r\n"),
+            Eol::Native if cfg!(windows) => Some("\r\n"),
+            Eol::Native => Some("\n"),
+            Eol::Keep => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bom {
+    Add,
+    Strip,
+    Keep,
+}
+
+impl Bom {
+    pub const VALUES: &'static [&'static str] = &["add", "strip", "keep"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "add" => Some(Bom::Add),
+            "strip" => Some(Bom::Strip),
+            "keep" => Some(Bom::Keep),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl Encoding {
+    pub const VALUES: &'static [&'static str] = &["utf-8", "utf-16le", "utf-16be", "latin1"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Some(Encoding::Utf8),
+            "utf-16" | "utf16" | "utf-16le" | "utf16le" => Some(Encoding::Utf16Le),
+            "utf-16be" | "utf16be" => Some(Encoding::Utf16Be),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(Encoding::Latin1),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextOptions {
+    pub eol: Eol,
+    pub bom: Bom,
+    pub encoding: Encoding,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        TextOptions {
+            eol: Eol::Keep,
+            bom: Bom::Keep,
+            encoding: Encoding::Utf8,
+        }
+    }
+}
+
+impl TextOptions {
+    /// Resolves options from the config file: per-type settings under
+    /// `[types.<ext>]` win over the top-level keys.
+    pub fn from_config(config: &Config, content_type: &ContentType) -> Result<Self, String> {
+        let type_config = config.types.get(content_type.extension());
+        let pick = |top: &Option<String>, per_type: Option<&Option<String>>| {
+            per_type.and_then(|v| v.clone()).or_else(|| top.clone())
+        };
+
+        let mut options = TextOptions::default();
+        if let Some(value) = pick(&config.eol, type_config.map(|t| &t.eol)) {
+            options.eol = Eol::parse(&value).ok_or_else(|| invalid("eol", &value))?;
+        }
+        if let Some(value) = pick(&config.bom, type_config.map(|t| &t.bom)) {
+            options.bom = Bom::parse(&value).ok_or_else(|| invalid("bom", &value))?;
+        }
+        if let Some(value) = pick(&config.encoding, type_config.map(|t| &t.encoding)) {
+            options.encoding =
+                Encoding::parse(&value).ok_or_else(|| invalid("encoding", &value))?;
+        }
+        Ok(options)
+    }
+
+    /// Rejects combinations no file can be written with, once config and
+    /// command line options are merged.
+    pub fn check(&self) -> Result<(), String> {
+        if self.bom == Bom::Add && self.encoding == Encoding::Latin1 {
+            return Err(NO_LATIN1_BOM.to_string());
+        }
+        Ok(())
+    }
+
+    /// Splits a leading byte order mark off `text`, and decides whether the
+    /// output gets one.
+    fn split_bom<'a>(&self, text: &'a str) -> (bool, &'a str) {
+        let (had_bom, body) = match text.strip_prefix(BOM) {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let with_bom = match self.bom {
+            Bom::Add => true,
+            Bom::Strip => false,
+            Bom::Keep => had_bom,
+        };
+        (with_bom, body)
+    }
+}
+
+const NO_LATIN1_BOM: &str = "Latin-1 has no byte order mark, use bom \"strip\" or \"keep\"";
+
+fn invalid(key: &str, value: &str) -> String {
+    format!("Invalid value '{value}' for '{key}' in config file")
+}
+
+/// Bytes encoded text is collected in before it goes to the writer.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Fails on the first character `options.encoding` can't represent,
+/// ignoring a leading byte order mark `options.bom` will strip. Run before
+/// opening the output, since `write_text` only notices once it gets there.
+pub fn check_encodable(text: &str, options: &TextOptions) -> Result<(), String> {
+    if options.encoding != Encoding::Latin1 {
+        return Ok(());
+    }
+    let (with_bom, body) = options.split_bom(text);
+    if with_bom {
+        return Err(NO_LATIN1_BOM.to_string());
+    }
+    // line numbers are the same with or without the mark
+    match body.char_indices().find(|(_, ch)| *ch as u32 > 0xFF) {
+        Some((index, ch)) => Err(unencodable(ch, body[..index].matches('\n').count() + 1)),
+        None => Ok(()),
+    }
+}
+
+fn unencodable(ch: char, line: usize) -> String {
+    format!(
+        "Cannot encode '{ch}' (U+{:04X}) on line {line} as Latin-1",
+        ch as u32
+    )
+}
+
+/// Encodes `text` into `writer` a chunk at a time, so the output never
+/// needs a second full-size copy of the text.
+pub fn write_text(writer: &mut dyn Write, text: &str, options: &TextOptions) -> io::Result<()> {
+    let (with_bom, body) = options.split_bom(text);
+    let mut out = TextWriter {
+        writer,
+        encoding: options.encoding,
+        buffer: Vec::with_capacity(CHUNK_SIZE + 4),
+        line: 1,
+    };
+    if with_bom {
+        out.push(BOM)?;
+    }
+    match options.eol.separator() {
+        Some(separator) => {
+            let mut chars = body.chars().peekable();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\r' => {
+                        if chars.peek() == Some(&'\n') {
+                            chars.next();
+                        }
+                        out.push_str(separator)?;
+                    }
+                    '\n' => out.push_str(separator)?,
+                    _ => out.push(ch)?,
+                }
+            }
+        }
+        None => out.push_str(body)?,
+    }
+    out.flush()
+}
+
+struct TextWriter<'a> {
+    writer: &'a mut dyn Write,
+    encoding: Encoding,
+    buffer: Vec<u8>,
+    line: usize,
+}
+
+impl TextWriter<'_> {
+    fn push(&mut self, ch: char) -> io::Result<()> {
+        match self.encoding {
+            Encoding::Utf8 => {
+                let mut utf8 = [0; 4];
+                self.buffer
+                    .extend_from_slice(ch.encode_utf8(&mut utf8).as_bytes());
+            }
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let mut units = [0; 2];
+                for unit in ch.encode_utf16(&mut units) {
+                    let pair = if self.encoding == Encoding::Utf16Le {
+                        unit.to_le_bytes()
+                    } else {
+                        unit.to_be_bytes()
+                    };
+                    self.buffer.extend_from_slice(&pair);
+                }
+            }
+            Encoding::Latin1 => {
+                if ch as u32 > 0xFF {
+                    let message = unencodable(ch, self.line);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+                }
+                self.buffer.push(ch as u8);
+            }
+        }
+        if ch == '\n' {
+            self.line += 1;
+        }
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn push_str(&mut self, text: &str) -> io::Result<()> {
+        if self.encoding == Encoding::Utf8 {
+            // already in the right shape; big runs skip the buffer
+            if text.len() >= CHUNK_SIZE {
+                self.flush()?;
+                return self.writer.write_all(text.as_bytes());
+            }
+            self.buffer.extend_from_slice(text.as_bytes());
+            if self.buffer.len() >= CHUNK_SIZE {
+                self.flush()?;
+            }
+            return Ok(());
+        }
+        text.chars().try_for_each(|ch| self.push(ch))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "encoding_tests.rs"]
+mod tests;
@@ -0,0 +1,153 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType;
+use crate::encoding::*;
+use c2f::parse_config_from_str;
+
+fn options(eol: Eol, bom: Bom, encoding: Encoding) -> TextOptions {
+    TextOptions { eol, bom, encoding }
+}
+
+fn encode_text(text: &str, options: &TextOptions) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    write_text(&mut bytes, text, options).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+#[test]
+fn test_default_options_keep_text_unchanged() {
+    let text = "line one\r\nline two\n";
+    let bytes = encode_text(text, &TextOptions::default()).unwrap();
+    assert_eq!(bytes, text.as_bytes());
+}
+
+#[test]
+fn test_eol_conversion() {
+    let text = "a\r\nb\nc\rd";
+    let lf = encode_text(text, &options(Eol::Lf, Bom::Keep, Encoding::Utf8)).unwrap();
+    assert_eq!(lf, b"a\nb\nc\nd");
+
+    let crlf = encode_text(text, &options(Eol::Crlf, Bom::Keep, Encoding::Utf8)).unwrap();
+    assert_eq!(crlf, b"a\r\nb\r\nc\r\nd");
+
+    let native = encode_text("a\nb", &options(Eol::Native, Bom::Keep, Encoding::Utf8)).unwrap();
+    if cfg!(windows) {
+        assert_eq!(native, b"a\r\nb");
+    } else {
+        assert_eq!(native, b"a\nb");
+    }
+}
+
+#[test]
+fn test_bom_handling() {
+    let add = encode_text("hi", &options(Eol::Keep, Bom::Add, Encoding::Utf8)).unwrap();
+    assert_eq!(add, b"\xEF\xBB\xBFhi");
+
+    let strip = encode_text(
+        "\u{feff}hi",
+        &options(Eol::Keep, Bom::Strip, Encoding::Utf8),
+    )
+    .unwrap();
+    assert_eq!(strip, b"hi");
+
+    let keep = encode_text("\u{feff}hi", &TextOptions::default()).unwrap();
+    assert_eq!(keep, b"\xEF\xBB\xBFhi");
+}
+
+#[test]
+fn test_utf16_encoding() {
+    let le = encode_text("hé", &options(Eol::Keep, Bom::Add, Encoding::Utf16Le)).unwrap();
+    assert_eq!(le, vec![0xFF, 0xFE, b'h', 0x00, 0xE9, 0x00]);
+
+    let be = encode_text("hé", &options(Eol::Keep, Bom::Keep, Encoding::Utf16Be)).unwrap();
+    assert_eq!(be, vec![0x00, b'h', 0x00, 0xE9]);
+}
+
+#[test]
+fn test_latin1_encoding() {
+    let bytes = encode_text("café", &options(Eol::Keep, Bom::Keep, Encoding::Latin1)).unwrap();
+    assert_eq!(bytes, vec![b'c', b'a', b'f', 0xE9]);
+
+    let err = encode_text("ok\n€", &options(Eol::Keep, Bom::Keep, Encoding::Latin1)).unwrap_err();
+    assert!(err.contains("U+20AC"));
+    assert!(err.contains("line 2"));
+}
+
+#[test]
+fn test_parse_values() {
+    assert_eq!(Eol::parse("CRLF"), Some(Eol::Crlf));
+    assert_eq!(Eol::parse("cr"), None);
+    assert_eq!(Bom::parse("strip"), Some(Bom::Strip));
+    assert_eq!(Encoding::parse("UTF_16BE"), Some(Encoding::Utf16Be));
+    assert_eq!(Encoding::parse("iso-8859-1"), Some(Encoding::Latin1));
+    assert_eq!(Encoding::parse("ebcdic"), None);
+}
+
+#[test]
+fn test_options_from_config() {
+    let config = parse_config_from_str(
+        r#"
+        eol = "lf"
+
+        [types.ps1]
+        eol = "crlf"
+        bom = "add"
+        "#,
+    );
+
+    let json = TextOptions::from_config(&config, &ContentType::Json).unwrap();
+    assert_eq!(json, options(Eol::Lf, Bom::Keep, Encoding::Utf8));
+
+    let ps1 = TextOptions::from_config(&config, &ContentType::PowerShell).unwrap();
+    assert_eq!(ps1, options(Eol::Crlf, Bom::Add, Encoding::Utf8));
+
+    let invalid = parse_config_from_str("encoding = \"ebcdic\"");
+    assert!(TextOptions::from_config(&invalid, &ContentType::Json).is_err());
+}
+
+#[test]
+fn test_check_encodable() {
+    let latin1 = options(Eol::Keep, Bom::Keep, Encoding::Latin1);
+    assert!(check_encodable("café", &latin1).is_ok());
+    assert!(check_encodable("€", &TextOptions::default()).is_ok());
+    let err = check_encodable("ok\nok\n€", &latin1).unwrap_err();
+    assert!(err.contains("U+20AC") && err.contains("line 3"));
+}
+
+#[test]
+fn test_check_encodable_byte_order_mark() {
+    // a mark that will be stripped is no reason to fail
+    let strip = options(Eol::Keep, Bom::Strip, Encoding::Latin1);
+    assert!(check_encodable("\u{feff}café", &strip).is_ok());
+    assert_eq!(encode_text("\u{feff}café", &strip).unwrap(), b"caf\xE9");
+
+    // one that would be kept is caught before the file is opened
+    let keep = options(Eol::Keep, Bom::Keep, Encoding::Latin1);
+    let err = check_encodable("\u{feff}café", &keep).unwrap_err();
+    assert!(err.contains("byte order mark"));
+    assert!(encode_text("\u{feff}café", &keep).is_err());
+}
+
+#[test]
+fn test_options_check() {
+    assert!(options(Eol::Keep, Bom::Add, Encoding::Utf8).check().is_ok());
+    assert!(options(Eol::Keep, Bom::Strip, Encoding::Latin1)
+        .check()
+        .is_ok());
+    let err = options(Eol::Keep, Bom::Add, Encoding::Latin1)
+        .check()
+        .unwrap_err();
+    assert!(err.contains("Latin-1"));
+}
+
+#[test]
+fn test_large_text_across_chunks() {
+    let text = "é line\r\n".repeat(50_000);
+    let crlf = encode_text(&text, &options(Eol::Lf, Bom::Keep, Encoding::Utf8)).unwrap();
+    assert_eq!(crlf, text.replace("\r\n", "\n").into_bytes());
+
+    let utf16 = encode_text(&text, &options(Eol::Keep, Bom::Keep, Encoding::Utf16Le)).unwrap();
+    let expected: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    assert_eq!(utf16, expected);
+}
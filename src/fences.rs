@@ -0,0 +1,122 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Fenced code blocks in Markdown, for --extract-code.
+
+use crate::detect::{detect_text_type_with, detectors, ContentType, DetectOptions};
+
+#[derive(Debug, PartialEq)]
+pub struct CodeBlock {
+    /// The info string after the opening fence, e.g. `rust` or
+    /// `python title="app.py"`.
+    pub info: String,
+    /// Contents with the fence's indentation removed, each line ending in
+    /// a line break.
+    pub body: String,
+    /// Index of the opening fence's line.
+    pub line: usize,
+}
+
+impl CodeBlock {
+    /// The language named by the info string, or detected from the body.
+    pub fn content_type(&self, options: &DetectOptions) -> ContentType {
+        let language = self
+            .info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{' || c == '}')
+            .find(|word| !word.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+        if !language.is_empty() {
+            let custom = options.custom.iter().map(|d| &d.content_type).find(|t| {
+                t.extension().eq_ignore_ascii_case(&language)
+                    || t.to_string().eq_ignore_ascii_case(&language)
+            });
+            let named = custom
+                .cloned()
+                .or_else(|| ContentType::from_name(&language))
+                .or_else(|| detectors::hints::interpreter_type(&language));
+            if let Some(content_type) = named {
+                return content_type;
+            }
+        }
+        detect_text_type_with(&self.body, options)
+    }
+}
+
+/// Finds the ``` and ~~~ fenced blocks in `text`, in order. A fence closes
+/// with at least as many of the same character; one left open runs to the
+/// end. Indented fences, as in list items, are accepted too.
+pub fn code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((start, line)) = lines.next() {
+        let Some((indent, fence, info)) = opening_fence(line) else {
+            continue;
+        };
+
+        let mut body = String::new();
+        for (_, line) in lines.by_ref() {
+            if closes(line, fence) {
+                break;
+            }
+            body.push_str(strip_indent(line, indent));
+            body.push('\n');
+        }
+        blocks.push(CodeBlock {
+            info: info.to_string(),
+            body,
+            line: start,
+        });
+    }
+    blocks
+}
+
+/// `name` for block `n` when one filename covers several blocks:
+/// `snippet.py` becomes `snippet-2.py`, `out` becomes `out-2`.
+pub fn numbered_filename(name: &str, n: usize) -> String {
+    let file_start = name.rfind('/').map_or(0, |i| i + 1);
+    // a leading dot is part of the stem, as in .env
+    let stem_end = name[file_start..]
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '.')
+        .map_or(name.len(), |(i, _)| file_start + i);
+    format!("{}-{n}{}", &name[..stem_end], &name[stem_end..])
+}
+
+/// Indentation, fence and info string of an opening fence line.
+fn opening_fence(line: &str) -> Option<(usize, &str, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    if length < 3 {
+        return None;
+    }
+    let (fence, info) = trimmed.split_at(length);
+    // a backtick in the info string means inline code, not a fence
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((indent, fence, info.trim()))
+}
+
+fn closes(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.as_bytes()[0] as char;
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == marker)
+}
+
+/// Removes up to `indent` leading whitespace characters.
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let strip = line
+        .char_indices()
+        .take(indent)
+        .find(|(_, c)| !c.is_whitespace())
+        .map_or_else(|| line.len().min(indent), |(i, _)| i);
+    &line[strip..]
+}
+
+#[cfg(test)]
+#[path = "fences_tests.rs"]
+mod tests;
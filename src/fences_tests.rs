@@ -0,0 +1,87 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::{ContentType, DetectOptions};
+use crate::fences::*;
+
+#[test]
+fn test_code_blocks() {
+    let markdown = "Here's the fix:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\nThen run:\n\n~~~\ncargo run\n~~~\n";
+    let blocks = code_blocks(markdown);
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].info, "rust");
+    assert_eq!(blocks[0].body, "fn main() {\n    println!(\"hi\");\n}\n");
+    assert_eq!(blocks[1].info, "");
+    assert_eq!(blocks[1].body, "cargo run\n");
+    assert_eq!((blocks[0].line, blocks[1].line), (2, 10));
+
+    assert!(code_blocks("no code here, just `inline` and ``` inline ```").is_empty());
+}
+
+#[test]
+fn test_nested_and_unclosed_fences() {
+    // a longer fence contains shorter ones; ~~~ doesn't close ```
+    let markdown = "````markdown\n```python\nprint(1)\n```\n~~~\n````\n";
+    let blocks = code_blocks(markdown);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].body, "```python\nprint(1)\n```\n~~~\n");
+
+    // an unclosed fence runs to the end
+    let blocks = code_blocks("text\n```sh\necho hi\n");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].body, "echo hi\n");
+
+    // CRLF line endings
+    let blocks = code_blocks("```\r\na\r\nb\r\n```\r\n");
+    assert_eq!(blocks[0].body, "a\nb\n");
+}
+
+#[test]
+fn test_indented_fences() {
+    // fences in list items lose the list indentation
+    let markdown = "1. Install:\n   ```bash\n   npm install\n     --save\n   ```\n";
+    let blocks = code_blocks(markdown);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].info, "bash");
+    assert_eq!(blocks[0].body, "npm install\n  --save\n");
+}
+
+#[test]
+fn test_block_content_type() {
+    let options = DetectOptions::default();
+    let block = |info: &str, body: &str| CodeBlock {
+        info: info.to_string(),
+        body: body.to_string(),
+        line: 0,
+    };
+
+    // the info string wins over the body
+    assert_eq!(
+        block("python", "x").content_type(&options),
+        ContentType::Python
+    );
+    assert_eq!(
+        block("yml {title=\"ci\"}", "x").content_type(&options),
+        ContentType::Yaml
+    );
+    assert_eq!(block("JSON", "x").content_type(&options), ContentType::Json);
+    // interpreter names work too
+    assert_eq!(block("zsh", "x").content_type(&options), ContentType::Shell);
+
+    // unknown or missing languages fall back to detection
+    let body = "{\"name\": \"c2f\", \"version\": 1}\n";
+    assert_eq!(block("", body).content_type(&options), ContentType::Json);
+    assert_eq!(
+        block("made-up-lang", body).content_type(&options),
+        ContentType::Json
+    );
+}
+
+#[test]
+fn test_numbered_filename() {
+    assert_eq!(numbered_filename("snippet.py", 2), "snippet-2.py");
+    assert_eq!(numbered_filename("out", 1), "out-1");
+    assert_eq!(numbered_filename("src/app.test.js", 3), "src/app-3.test.js");
+    assert_eq!(numbered_filename("v1.2/main", 1), "v1.2/main-1");
+    assert_eq!(numbered_filename(".env", 2), ".env-2");
+}
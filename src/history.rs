@@ -0,0 +1,242 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const INDEX_FILE: &str = "history.jsonl";
+const ENTRIES_DIR: &str = "entries";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: u64,
+    pub timestamp: u64,
+    pub content_type: String,
+    pub size: u64,
+    pub hash: String,
+    pub path: String,
+    // whether a copy of the content is kept for restore
+    #[serde(default)]
+    pub stored: bool,
+}
+
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    pub fn open(dir: PathBuf) -> Self {
+        History { dir }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join(INDEX_FILE)
+    }
+
+    fn entry_path(&self, id: u64) -> PathBuf {
+        self.dir.join(ENTRIES_DIR).join(id.to_string())
+    }
+
+    pub fn entries(&self) -> Result<Vec<Entry>, String> {
+        let contents = match fs::read_to_string(self.index_path()) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Error reading history: {e}")),
+        };
+
+        // skip lines we can't parse rather than losing the whole history
+        Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Records a saved entry. `content` is kept for `restore` when given.
+    pub fn record(
+        &self,
+        content_type: &str,
+        size: u64,
+        hash: String,
+        path: &Path,
+        content: Option<&[u8]>,
+    ) -> Result<Entry, String> {
+        let entries = self.entries()?;
+        let entry = Entry {
+            id: entries.iter().map(|e| e.id).max().unwrap_or(0) + 1,
+            timestamp: now(),
+            content_type: content_type.to_string(),
+            size,
+            hash,
+            path: fs::canonicalize(path)
+                .unwrap_or_else(|_| path.to_path_buf())
+                .display()
+                .to_string(),
+            stored: content.is_some(),
+        };
+
+        if let Some(content) = content {
+            let entry_path = self.entry_path(entry.id);
+            create_dir_private(entry_path.parent().unwrap())?;
+            write_private(&entry_path, content, false)?;
+        }
+
+        let mut line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+        line.push('\n');
+        create_dir_private(&self.dir)?;
+        write_private(&self.index_path(), line.as_bytes(), true)?;
+
+        Ok(entry)
+    }
+
+    pub fn search(&self, term: &str) -> Result<Vec<Entry>, String> {
+        let term = term.to_lowercase();
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| {
+                entry.path.to_lowercase().contains(&term)
+                    || entry.content_type.to_lowercase().contains(&term)
+                    || self
+                        .stored_content(entry)
+                        .ok()
+                        .and_then(|bytes| String::from_utf8(bytes).ok())
+                        .map_or(false, |text| text.to_lowercase().contains(&term))
+            })
+            .collect())
+    }
+
+    pub fn content(&self, id: u64) -> Result<Vec<u8>, String> {
+        let entry = self
+            .entries()?
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| format!("No history entry with id {id}"))?;
+        self.stored_content(&entry)
+    }
+
+    // takes the entry already read, so `search` reads the index only once
+    fn stored_content(&self, entry: &Entry) -> Result<Vec<u8>, String> {
+        let id = entry.id;
+        if !entry.stored {
+            return Err(format!(
+                "History entry {id} has no stored content (encrypted or sensitive)"
+            ));
+        }
+        fs::read(self.entry_path(id)).map_err(|e| format!("Error reading history entry {id}: {e}"))
+    }
+
+    /// Removes entries older than `age`, returning how many were removed.
+    pub fn prune(&self, age: Duration) -> Result<usize, String> {
+        self.prune_at(age, now())
+    }
+
+    fn prune_at(&self, age: Duration, now: u64) -> Result<usize, String> {
+        let cutoff = now.saturating_sub(age.as_secs());
+        let (keep, remove): (Vec<Entry>, Vec<Entry>) = self
+            .entries()?
+            .into_iter()
+            .partition(|e| e.timestamp >= cutoff);
+        if remove.is_empty() {
+            return Ok(0);
+        }
+
+        for entry in &remove {
+            fs::remove_file(self.entry_path(entry.id)).ok();
+        }
+        let mut contents = String::new();
+        for entry in &keep {
+            contents.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+            contents.push('\n');
+        }
+        write_private(&self.index_path(), contents.as_bytes(), false)?;
+
+        Ok(remove.len())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn create_dir_private(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Error creating '{}': {e}", dir.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700)).ok();
+    }
+    Ok(())
+}
+
+fn write_private(path: &Path, bytes: &[u8], append: bool) -> Result<(), String> {
+    crate::output::open_output(path, append, Some(crate::output::PRIVATE_MODE))
+        .and_then(|mut file| file.write_all(bytes))
+        .map_err(|e| format!("Error writing '{}': {e}", path.display()))
+}
+
+/// FNV-1a, hex encoded. Only used to spot duplicate entries.
+pub fn hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// Parses ages like `30d`, `12h`, `2w`, `45m` or `90s`.
+pub fn parse_age(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(number.checked_mul(seconds)?))
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` in UTC.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let secs = timestamp % 86400;
+
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+pub fn format_size(size: u64) -> String {
+    match size {
+        s if s < 1024 => format!("{s} B"),
+        s if s < 1024 * 1024 => format!("{:.1} KB", s as f64 / 1024.0),
+        s => format!("{:.1} MB", s as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod tests;
@@ -0,0 +1,117 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::history::*;
+use std::fs;
+use std::time::Duration;
+
+fn temp_history() -> (tempfile::TempDir, History) {
+    let dir = tempfile::tempdir().unwrap();
+    let history = History::open(dir.path().join("c2f"));
+    (dir, history)
+}
+
+#[test]
+fn test_empty_history() {
+    let (_dir, history) = temp_history();
+    assert!(history.entries().unwrap().is_empty());
+    assert!(history.content(1).is_err());
+}
+
+#[test]
+fn test_record_and_restore() {
+    let (dir, history) = temp_history();
+    let path = dir.path().join("clipboard.json");
+    fs::write(&path, "{}").unwrap();
+
+    let first = history
+        .record("JSON", 2, hash(b"{}"), &path, Some(b"{}"))
+        .unwrap();
+    let second = history
+        .record("Environment file", 9, hash(b"KEY=value"), &path, None)
+        .unwrap();
+    assert_eq!(first.id, 1);
+    assert_eq!(second.id, 2);
+
+    let entries = history.entries().unwrap();
+    assert_eq!(entries, vec![first, second]);
+    assert_eq!(history.content(1).unwrap(), b"{}");
+    assert!(history
+        .content(2)
+        .unwrap_err()
+        .contains("no stored content"));
+}
+
+#[test]
+fn test_search() {
+    let (dir, history) = temp_history();
+    let path = dir.path().join("notes.md");
+    history
+        .record("Markdown", 7, hash(b"# Hello"), &path, Some(b"# Hello"))
+        .unwrap();
+    history
+        .record(
+            "Python",
+            12,
+            hash(b"print('hi')"),
+            &path,
+            Some(b"print('hi')"),
+        )
+        .unwrap();
+
+    let ids = |term| -> Vec<u64> { history.search(term).unwrap().iter().map(|e| e.id).collect() };
+    assert_eq!(ids("hello"), vec![1]);
+    assert_eq!(ids("python"), vec![2]);
+    assert_eq!(ids("notes.md"), vec![1, 2]);
+    assert!(ids("missing").is_empty());
+}
+
+#[test]
+fn test_prune() {
+    let (dir, history) = temp_history();
+    let path = dir.path().join("a.txt");
+    let entry = history
+        .record("Plain text", 1, hash(b"a"), &path, Some(b"a"))
+        .unwrap();
+
+    assert_eq!(history.prune(Duration::from_secs(3600)).unwrap(), 0);
+    assert_eq!(history.entries().unwrap().len(), 1);
+
+    // a second later, anything older than 0s goes
+    let later = entry.timestamp + 1;
+    assert_eq!(history.prune_at(Duration::from_secs(0), later).unwrap(), 1);
+    assert!(history.entries().unwrap().is_empty());
+    assert!(history.content(1).is_err());
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(hash(b""), "cbf29ce484222325");
+    assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+    assert_ne!(hash(b"a"), hash(b"b"));
+}
+
+#[test]
+fn test_parse_age() {
+    assert_eq!(parse_age("30d"), Some(Duration::from_secs(30 * 86400)));
+    assert_eq!(parse_age("12h"), Some(Duration::from_secs(12 * 3600)));
+    assert_eq!(parse_age("2w"), Some(Duration::from_secs(14 * 86400)));
+    assert_eq!(parse_age("90s"), Some(Duration::from_secs(90)));
+    assert_eq!(parse_age("30"), None);
+    assert_eq!(parse_age("d"), None);
+    assert_eq!(parse_age("5y"), None);
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+    assert_eq!(format_timestamp(1736517780), "2025-01-10 14:03");
+    assert_eq!(format_timestamp(951782400), "2000-02-29 00:00");
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(2048), "2.0 KB");
+    assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+}
@@ -0,0 +1,784 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// HTML to Markdown, for saving browser copies to .md files. The HTML is
+// parsed leniently into a small tree (unclosed <p>, <li> and table cells
+// are closed the way browsers do) and rendered block by block: headings,
+// paragraphs, lists, quotes, code blocks and tables become their Markdown
+// forms, anything unknown is reduced to its text.
+
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+// elements whose contents never make it into the Markdown
+const SKIPPED: &[&str] = &[
+    "head", "script", "style", "template", "noscript", "iframe", "svg", "button", "select",
+];
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// Converts an HTML document or fragment to Markdown.
+pub fn to_markdown(html: &str) -> String {
+    let blocks = render_blocks(&parse(strip_header(html)));
+    let markdown = blocks.join("\n\n");
+    if markdown.is_empty() {
+        markdown
+    } else {
+        format!("{markdown}\n")
+    }
+}
+
+/// Whether the HTML is only `<div>` and `<span>` runs styled with
+/// `white-space: pre`, which is how code editors such as VS Code copy
+/// source with its highlighting. The plain text is the source itself, and
+/// converting the HTML would only escape it.
+pub fn is_styled_source(html: &str) -> bool {
+    let mut preformatted = false;
+    only_styled_runs(&parse(strip_header(html)), &mut preformatted) && preformatted
+}
+
+fn only_styled_runs(nodes: &[Node], preformatted: &mut bool) -> bool {
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+        let style = element.attr("style").unwrap_or_default().replace(' ', "");
+        if style.to_ascii_lowercase().contains("white-space:pre") {
+            *preformatted = true;
+        }
+        let allowed = matches!(
+            element.name.as_str(),
+            "html" | "head" | "body" | "meta" | "style" | "div" | "span" | "br" | "font"
+        );
+        if !allowed || !only_styled_runs(&element.children, preformatted) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Drops the "Version:0.9" header the Windows clipboard puts before HTML.
+fn strip_header(html: &str) -> &str {
+    match html.find('<') {
+        Some(start) if html.starts_with("Version:") => &html[start..],
+        _ => html,
+    }
+}
+
+// --- parsing ---
+
+fn parse(html: &str) -> Vec<Node> {
+    let mut stack = vec![Element {
+        name: String::new(),
+        attrs: Vec::new(),
+        children: Vec::new(),
+    }];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            close(&mut stack, &tag[..end].trim().to_ascii_lowercase());
+            rest = tag.get(end + 1..).unwrap_or_default();
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (element, self_closing, after) = parse_tag(&rest[1..]);
+            rest = after;
+            let name = element.name.clone();
+            if SKIPPED.contains(&name.as_str()) {
+                // drop everything up to the matching end tag
+                let end_tag = format!("</{name}");
+                rest = find_ignore_case(rest, &end_tag).map_or("", |end| {
+                    let after = &rest[end..];
+                    after.find('>').map_or("", |gt| &after[gt + 1..])
+                });
+                continue;
+            }
+            open(&mut stack, element);
+            if self_closing || VOID.contains(&name.as_str()) {
+                close(&mut stack, &name);
+            }
+        } else {
+            // a stray '<'
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+        }
+    }
+
+    while stack.len() > 1 {
+        pop(&mut stack);
+    }
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Parses a start tag after its '<', returning the element, whether it
+/// ended in "/>", and the text after it.
+fn parse_tag(tag: &str) -> (Element, bool, &str) {
+    let name_end = tag
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag.len());
+    let mut element = Element {
+        name: tag[..name_end].to_ascii_lowercase(),
+        attrs: Vec::new(),
+        children: Vec::new(),
+    };
+    let mut rest = &tag[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (element, true, after);
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return (element, false, after);
+        }
+        if rest.is_empty() {
+            return (element, false, rest);
+        }
+        if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(rest.chars().next().map_or(0, char::len_utf8));
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let body = &after[1..];
+                    let end = body.find(quote).unwrap_or(body.len());
+                    (&body[..end], body.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = remaining;
+        }
+        element.attrs.push((key, value));
+    }
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let text = decode_entities(text);
+    let parent = stack.last_mut().expect("root stays on the stack");
+    if let Some(Node::Text(previous)) = parent.children.last_mut() {
+        previous.push_str(&text);
+    } else {
+        parent.children.push(Node::Text(text));
+    }
+}
+
+fn open(stack: &mut Vec<Element>, element: Element) {
+    // tags that end an open element the way browsers do, e.g. <li><li>
+    let closes: &[&str] = match element.name.as_str() {
+        "li" => &["li"],
+        "dt" | "dd" => &["dt", "dd"],
+        "tr" => &["tr", "td", "th"],
+        "td" | "th" => &["td", "th"],
+        "thead" | "tbody" | "tfoot" => &["thead", "tbody", "tfoot", "tr", "td", "th"],
+        name if BLOCKS.contains(&name) => &["p"],
+        _ => &[],
+    };
+    let boundary = ["ul", "ol", "table", "dl", "blockquote", "div"];
+    if let Some(index) = stack.iter().rposition(|open| {
+        closes.contains(&open.name.as_str()) || boundary.contains(&open.name.as_str())
+    }) {
+        if closes.contains(&stack[index].name.as_str()) {
+            while stack.len() > index {
+                pop(stack);
+            }
+        }
+    }
+    stack.push(element);
+}
+
+fn close(stack: &mut Vec<Element>, name: &str) {
+    // an end tag without a matching start tag is ignored
+    if let Some(index) = stack.iter().skip(1).rposition(|open| open.name == name) {
+        while stack.len() > index + 1 {
+            pop(stack);
+        }
+    }
+}
+
+fn pop(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(Node::Element(element));
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity.strip_prefix('#') {
+                Some(number) => match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => named_entity(entity),
+            }?;
+            Some((ch, end + 1))
+        });
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn named_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "bull" => '•',
+        "middot" => '·',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "times" => '×',
+        "deg" => '°',
+        "euro" => '€',
+        _ => return None,
+    })
+}
+
+// --- rendering ---
+
+fn is_block(node: &Node) -> bool {
+    matches!(node, Node::Element(e) if BLOCKS.contains(&e.name.as_str()))
+}
+
+/// Renders nodes as Markdown blocks, to be separated by blank lines.
+/// Runs of inline content between block elements become paragraphs.
+fn render_blocks(nodes: &[Node]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut run: Vec<&Node> = Vec::new();
+    for node in nodes {
+        if !is_block(node) {
+            run.push(node);
+            continue;
+        }
+        flush_paragraph(&mut run, &mut blocks);
+        if let Node::Element(element) = node {
+            render_block(element, &mut blocks);
+        }
+    }
+    flush_paragraph(&mut run, &mut blocks);
+    blocks
+}
+
+fn flush_paragraph(run: &mut Vec<&Node>, blocks: &mut Vec<String>) {
+    let mut text = String::new();
+    for node in run.drain(..) {
+        render_inline(node, &mut text);
+    }
+    let paragraph = tidy_inline(&text);
+    if !paragraph.is_empty() {
+        blocks.push(escape_line_start(&paragraph));
+    }
+}
+
+fn render_block(element: &Element, blocks: &mut Vec<String>) {
+    match element.name.as_str() {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = usize::from(element.name.as_bytes()[1] - b'0');
+            let text = tidy_inline(&inline_text(&element.children)).replace('\n', " ");
+            if !text.is_empty() {
+                blocks.push(format!("{} {text}", "#".repeat(level)));
+            }
+        }
+        "hr" => blocks.push("---".to_string()),
+        "pre" => blocks.push(render_pre(element)),
+        "blockquote" => {
+            let inner = render_blocks(&element.children).join("\n\n");
+            if !inner.is_empty() {
+                let quoted: Vec<String> = inner
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {line}")
+                        }
+                    })
+                    .collect();
+                blocks.push(quoted.join("\n"));
+            }
+        }
+        "ul" | "ol" => {
+            let list = render_list(element);
+            if !list.is_empty() {
+                blocks.push(list);
+            }
+        }
+        "table" => {
+            let table = render_table(element);
+            if !table.is_empty() {
+                blocks.push(table);
+            }
+        }
+        "dt" => {
+            let text = tidy_inline(&inline_text(&element.children));
+            if !text.is_empty() {
+                blocks.push(format!("**{text}**"));
+            }
+        }
+        // containers: their contents are blocks of their own
+        _ => blocks.extend(render_blocks(&element.children)),
+    }
+}
+
+fn render_pre(element: &Element) -> String {
+    let mut code = String::new();
+    collect_text(&element.children, &mut code);
+    let code = code.strip_prefix('\n').unwrap_or(&code).trim_end();
+
+    // the language, from class="language-rust" on <pre> or its <code>
+    let code_child = element.children.iter().find_map(|node| match node {
+        Node::Element(e) if e.name == "code" => Some(e),
+        _ => None,
+    });
+    let language = [Some(element), code_child]
+        .iter()
+        .flatten()
+        .filter_map(|e| e.attr("class"))
+        .flat_map(str::split_whitespace)
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or_default();
+
+    let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+    format!("{fence}{language}\n{code}\n{fence}")
+}
+
+fn render_list(list: &Element) -> String {
+    let ordered = list.name == "ol";
+    let mut number: usize = list
+        .attr("start")
+        .and_then(|start| start.trim().parse().ok())
+        .unwrap_or(1);
+    let mut items: Vec<String> = Vec::new();
+    for node in &list.children {
+        let Node::Element(item) = node else {
+            continue;
+        };
+        let blocks = if item.name == "li" {
+            render_blocks(&item.children)
+        } else if item.name == "ul" || item.name == "ol" {
+            // a list nested directly in a list belongs to the previous item
+            if let Some(previous) = items.last_mut() {
+                previous.push('\n');
+                previous.push_str(&indent(&render_list(item), 2));
+            }
+            continue;
+        } else {
+            continue;
+        };
+
+        let marker = if ordered {
+            let marker = format!("{number}. ");
+            number += 1;
+            marker
+        } else {
+            "- ".to_string()
+        };
+        let body = blocks.join("\n");
+        let body = indent(&body, marker.len());
+        items.push(format!("{marker}{}", body.trim_start()));
+    }
+    items.join("\n")
+}
+
+fn indent(text: &str, width: usize) -> String {
+    let pad = " ".repeat(width);
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{pad}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_table(table: &Element) -> String {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    collect_rows(&table.children, &mut rows);
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let mut lines = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<&str> = (0..columns)
+            .map(|c| row.get(c).map_or("", String::as_str))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+        // Markdown tables always have a header: the first row
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+    lines.join("\n")
+}
+
+fn collect_rows(nodes: &[Node], rows: &mut Vec<Vec<String>>) {
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+        match element.name.as_str() {
+            "tr" => {
+                let cells = element
+                    .children
+                    .iter()
+                    .filter_map(|cell| match cell {
+                        Node::Element(e) if e.name == "td" || e.name == "th" => {
+                            let text = tidy_inline(&inline_text(&e.children));
+                            Some(text.replace('\n', " ").replace('|', "\\|"))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                rows.push(cells);
+            }
+            // nested tables aren't expressible, only their text is kept
+            "table" => {}
+            _ => collect_rows(&element.children, rows),
+        }
+    }
+}
+
+fn inline_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        render_inline(node, &mut out);
+    }
+    out
+}
+
+/// Renders inline content. Whitespace is collapsed later by `tidy_inline`;
+/// line breaks from <br> are kept as '\n'.
+fn render_inline(node: &Node, out: &mut String) {
+    let element = match node {
+        Node::Text(text) => {
+            out.push_str(&escape(&collapse_whitespace(text)));
+            return;
+        }
+        Node::Element(element) => element,
+    };
+    match element.name.as_str() {
+        "br" => out.push('\n'),
+        "strong" | "b" => wrap(&inline_text(&element.children), "**", out),
+        "em" | "i" => wrap(&inline_text(&element.children), "*", out),
+        "del" | "s" | "strike" => wrap(&inline_text(&element.children), "~~", out),
+        "code" | "kbd" | "samp" | "tt" => {
+            let mut code = String::new();
+            collect_text(&element.children, &mut code);
+            let code = collapse_whitespace(&code);
+            let code = code.trim();
+            if !code.is_empty() {
+                let ticks = "`".repeat(longest_run(code, '`') + 1);
+                let pad = if code.starts_with('`') || code.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+                out.push_str(&format!("{ticks}{pad}{code}{pad}{ticks}"));
+            }
+        }
+        "a" => {
+            let text = tidy_inline(&inline_text(&element.children)).replace('\n', " ");
+            let href = element.attr("href").unwrap_or_default().trim();
+            if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+                out.push_str(&text);
+            } else if text.is_empty() || text == escape(href) {
+                out.push_str(&format!("<{href}>"));
+            } else {
+                out.push_str(&format!("[{text}]({})", link_target(href)));
+            }
+        }
+        "img" => {
+            let src = element.attr("src").unwrap_or_default().trim();
+            if !src.is_empty() && !src.starts_with("data:") {
+                let alt = escape(&collapse_whitespace(
+                    element.attr("alt").unwrap_or_default(),
+                ));
+                out.push_str(&format!("![{}]({})", alt.trim(), link_target(src)));
+            }
+        }
+        // block elements inside inline content: keep them on their own line
+        name if BLOCKS.contains(&name) => {
+            out.push('\n');
+            out.push_str(&inline_text(&element.children));
+            out.push('\n');
+        }
+        _ => out.push_str(&inline_text(&element.children)),
+    }
+}
+
+/// Wraps emphasis markers around `inner`, keeping its surrounding spaces
+/// outside them: `<b> bold </b>` is " **bold** ", not "** bold **".
+fn wrap(inner: &str, marker: &str, out: &mut String) {
+    let core = inner.trim();
+    if core.is_empty() {
+        out.push_str(inner);
+        return;
+    }
+    if inner.starts_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(&format!("{marker}{core}{marker}"));
+    if inner.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+fn link_target(url: &str) -> String {
+    if url.contains([' ', '(', ')']) {
+        format!("<{url}>")
+    } else {
+        url.to_string()
+    }
+}
+
+fn collect_text(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(e) if e.name == "br" => out.push('\n'),
+            Node::Element(e) => {
+                collect_text(&e.children, out);
+                // lines of some highlighters are block elements
+                if (e.name == "div" || e.name == "p") && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+}
+
+fn longest_run(text: &str, ch: char) -> usize {
+    text.split(|c| c != ch).map(str::len).max().unwrap_or(0)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for ch in text.chars() {
+        // non-breaking spaces are kept as plain spaces
+        if ch.is_whitespace() {
+            space = true;
+        } else {
+            if space {
+                out.push(' ');
+                space = false;
+            }
+            out.push(ch);
+        }
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+/// Collapses the spaces left between inline pieces and trims each line;
+/// lines broken by <br> end in a backslash, Markdown's hard line break.
+fn tidy_inline(text: &str) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| collapse_whitespace(line).trim().to_string())
+        .collect();
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(String::as_str)
+        .skip_while(|line| line.is_empty())
+        .collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+    lines[..end]
+        .iter()
+        .filter(|line| !line.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\\\n")
+}
+
+/// Backslash-escapes characters that would otherwise turn text into
+/// Markdown syntax. Underscores inside words are left alone.
+fn escape(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &ch) in chars.iter().enumerate() {
+        let escaped = match ch {
+            '\\' | '*' | '`' | '[' | ']' => true,
+            '_' => {
+                let word = |c: Option<&char>| c.map_or(false, |c| c.is_alphanumeric());
+                !(word(i.checked_sub(1).and_then(|p| chars.get(p))) && word(chars.get(i + 1)))
+            }
+            '<' => chars
+                .get(i + 1)
+                .map_or(false, |c| c.is_ascii_alphabetic() || *c == '/' || *c == '!'),
+            _ => false,
+        };
+        if escaped {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Escapes a paragraph start that would read as a heading, quote or list.
+fn escape_line_start(paragraph: &str) -> String {
+    let first_word = paragraph.split(' ').next().unwrap_or_default();
+    let number = first_word
+        .strip_suffix(['.', ')'])
+        .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+    if let Some(number) = number {
+        // the digits are ASCII, so the marker is the next byte
+        let at = number.len();
+        return format!("{}\\{}", &paragraph[..at], &paragraph[at..]);
+    }
+    let needs_escape = paragraph.starts_with('#')
+        || paragraph.starts_with('>')
+        || ["- ", "+ "].iter().any(|m| paragraph.starts_with(m))
+        || paragraph == "-"
+        || paragraph.chars().all(|c| c == '-' || c == '=');
+    if needs_escape {
+        format!("\\{paragraph}")
+    } else {
+        paragraph.to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "html_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::html::*;
+
+#[test]
+fn test_headings_and_paragraphs() {
+    let html = "<h1>Title</h1><p>Some <b>bold</b> and <em>emphasised</em>\n   text.</p><h3>Sub &amp; more</h3><p>Line one<br>line two</p>";
+    assert_eq!(
+        to_markdown(html),
+        "# Title\n\nSome **bold** and *emphasised* text.\n\n### Sub & more\n\nLine one\\\nline two\n"
+    );
+}
+
+#[test]
+fn test_links_images_and_inline_code() {
+    let html = r#"<p>See <a href="https://example.com/docs">the docs</a>, <a href="https://x.io">https://x.io</a> and <img src="/a.png" alt="a chart">. Run <code>cargo build</code>.</p>"#;
+    assert_eq!(
+        to_markdown(html),
+        "See [the docs](https://example.com/docs), <https://x.io> and ![a chart](/a.png). Run `cargo build`.\n"
+    );
+    // spaces inside emphasis move outside the markers
+    assert_eq!(to_markdown("<p>a<b> b </b>c</p>"), "a **b** c\n");
+}
+
+#[test]
+fn test_lists() {
+    let html = "<ul><li>one<li>two<ul><li>nested</li></ul></li></ul><ol start=\"3\"><li><p>three</p></li><li>four</li></ol>";
+    assert_eq!(
+        to_markdown(html),
+        "- one\n- two\n  - nested\n\n3. three\n4. four\n"
+    );
+}
+
+#[test]
+fn test_code_blocks() {
+    let html = "<pre><code class=\"language-rust\">fn main() {\n    println!(\"&lt;hi&gt;\");\n}\n</code></pre>";
+    assert_eq!(
+        to_markdown(html),
+        "```rust\nfn main() {\n    println!(\"<hi>\");\n}\n```\n"
+    );
+    // code containing a fence gets a longer one
+    assert_eq!(
+        to_markdown("<pre>```\nx\n```</pre>"),
+        "````\n```\nx\n```\n````\n"
+    );
+}
+
+#[test]
+fn test_tables() {
+    let html = "<table><thead><tr><th>Name</th><th>Note</th></tr></thead><tbody><tr><td>a</td><td>x | y</td></tr><tr><td>b</td></tr></tbody></table>";
+    assert_eq!(
+        to_markdown(html),
+        "| Name | Note |\n| --- | --- |\n| a | x \\| y |\n| b |  |\n"
+    );
+}
+
+#[test]
+fn test_quotes_and_rules() {
+    assert_eq!(
+        to_markdown("<blockquote><p>one</p><p>two</p></blockquote><hr><p>after</p>"),
+        "> one\n>\n> two\n\n---\n\nafter\n"
+    );
+}
+
+#[test]
+fn test_browser_clipboard_html() {
+    // what browsers put on the clipboard: a fragment with markers and styling
+    let html = "<html><head><style>p { color: red }</style></head><body>\
+        <!--StartFragment--><div class=\"post\"><p>Hello&nbsp;world</p>\
+        <script>alert(1)</script><span>trailing</span></div><!--EndFragment--></body></html>";
+    // non-breaking spaces become plain ones
+    assert_eq!(to_markdown(html), "Hello world\n\ntrailing\n");
+
+    // the Windows clipboard format header is dropped
+    let windows = "Version:0.9\r\nStartHTML:0000000105\r\n<html><body><p>hi</p></body></html>";
+    assert_eq!(to_markdown(windows), "hi\n");
+}
+
+#[test]
+fn test_escaping() {
+    assert_eq!(
+        to_markdown("<p>2 * 3 = 6, snake_case, _x_ and [brackets]</p>"),
+        "2 \\* 3 = 6, snake_case, \\_x\\_ and \\[brackets\\]\n"
+    );
+    assert_eq!(to_markdown("<p># not a heading</p>"), "\\# not a heading\n");
+    assert_eq!(to_markdown("<p>1. not a list</p>"), "1\\. not a list\n");
+    assert_eq!(to_markdown("<p>a < b &lt;tag&gt;</p>"), "a < b \\<tag>\n");
+    assert_eq!(to_markdown("<p>12) not a list</p>"), "12\\) not a list\n");
+}
+
+#[test]
+fn test_non_ascii_paragraphs() {
+    assert_eq!(to_markdown("<p>Café is open</p>"), "Café is open\n");
+    assert_eq!(to_markdown("<p>日本語 text</p>"), "日本語 text\n");
+    assert_eq!(to_markdown("<p>é.</p><p>١. x</p>"), "é.\n\n١. x\n");
+    assert_eq!(to_markdown("<p é=\"1\">attribute</p>"), "attribute\n");
+}
+
+#[test]
+fn test_malformed_html() {
+    assert_eq!(to_markdown("<p>one<p>two"), "one\n\ntwo\n");
+    assert_eq!(to_markdown("text</b> only"), "text only\n");
+    assert_eq!(to_markdown("<p>cut off <a href=\"x"), "cut off <x>\n");
+    assert_eq!(to_markdown(""), "");
+}
+
+#[test]
+fn test_styled_source() {
+    // what VS Code puts on the clipboard for a copied Markdown file
+    let vscode = "<meta charset='utf-8'><div style=\"color: #d4d4d4;background-color: #1e1e1e;\
+        font-family: Menlo, Monaco, 'Courier New', monospace;font-weight: normal;font-size: 12px;\
+        line-height: 18px;white-space: pre;\"><div><span style=\"color: #569cd6;font-weight: bold;\">\
+        # Title</span></div><br><div><span style=\"color: #d4d4d4;\">Some </span>\
+        <span style=\"color: #569cd6;font-weight: bold;\">**bold**</span></div></div>";
+    assert!(is_styled_source(vscode));
+    assert!(is_styled_source(&format!(
+        "Version:0.9\r\nStartHTML:0\r\n{vscode}"
+    )));
+
+    // a browser copy of a page
+    assert!(!is_styled_source("<h1>Title</h1><p>Some <b>bold</b></p>"));
+    // spans without preformatting are rich text
+    assert!(!is_styled_source(
+        "<div><span style=\"color: red\">note</span></div>"
+    ));
+}
@@ -2,6 +2,7 @@
 // Licensed under the MIT License
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -13,18 +14,120 @@ pub struct Config {
     pub quiet: bool,
     #[serde(default = "default_true")]
     pub detect_type: bool,
+    // detection looks at most at this many bytes of the start and end; 0 = all
+    #[serde(default = "default_detect_max_bytes")]
+    pub detect_max_bytes: usize,
+    #[serde(default = "default_on_secret")]
+    pub on_secret: String,
+    // warn, prompt, fail or ignore when an explicit filename's extension
+    // contradicts detection
+    #[serde(default = "default_on_type_mismatch")]
+    pub on_type_mismatch: String,
+    // convert copied HTML when saving to a .md file
+    #[serde(default = "default_true")]
+    pub html_to_markdown: bool,
+    // sanitize text saved as code even without --sanitize
+    #[serde(default)]
+    pub sanitize_code: bool,
+    // octal permissions for created files, e.g. "600"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_mode: Option<String>,
+    #[serde(default)]
+    pub encrypt: bool,
+    // age public keys; a passphrase is used when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_file: Option<String>,
+    #[serde(default)]
+    pub history: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    // per content type overrides, keyed by extension
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<String, TypeConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_types: Vec<CustomType>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TypeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bom: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+/// A user-defined content type. Every rule that is set must match.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomType {
+    pub name: String,
+    pub extension: String,
+    // higher runs first; negative runs after the built-in detectors
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    // interpreter named on a `#!` first line, e.g. "nix-shell"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shebang: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    // how many of `keywords` must appear (default: 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_keywords: Option<usize>,
+    // content must parse as JSON
+    #[serde(default)]
+    pub json: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
+pub const DEFAULT_DETECT_MAX_BYTES: usize = 64 * 1024;
+
+fn default_detect_max_bytes() -> usize {
+    DEFAULT_DETECT_MAX_BYTES
+}
+
+fn default_on_secret() -> String {
+    "warn".to_string()
+}
+
+fn default_on_type_mismatch() -> String {
+    "warn".to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             ask_confirmation: false,
             quiet: false,
             detect_type: true,
+            detect_max_bytes: DEFAULT_DETECT_MAX_BYTES,
+            on_secret: default_on_secret(),
+            on_type_mismatch: default_on_type_mismatch(),
+            html_to_markdown: true,
+            sanitize_code: false,
+            file_mode: None,
+            encrypt: false,
+            recipients: Vec::new(),
+            identity_file: None,
+            history: false,
+            eol: None,
+            bom: None,
+            encoding: None,
+            types: BTreeMap::new(),
+            custom_types: Vec::new(),
         }
     }
 }
@@ -37,7 +140,18 @@ pub fn get_config_path() -> PathBuf {
     path
 }
 
-pub fn load_config() -> Config {
+pub fn get_history_dir() -> PathBuf {
+    let mut path = dirs::home_dir().expect("Could not find home directory");
+    path.push(".local");
+    path.push("share");
+    path.push("c2f");
+    path
+}
+
+/// Reads the config file, creating it with defaults if it doesn't exist. A
+/// file that can't be read or parsed is an error rather than a silent return
+/// to defaults, which would drop settings like `encrypt` and `on_secret`.
+pub fn load_config() -> Result<Config, String> {
     let config_path = get_config_path();
 
     if !config_path.exists() {
@@ -51,11 +165,17 @@ pub fn load_config() -> Config {
         let toml_string = toml::to_string(&default_config).unwrap();
         fs::write(&config_path, toml_string).ok();
 
-        return default_config;
+        return Ok(default_config);
     }
 
-    let contents = fs::read_to_string(&config_path).unwrap_or_default();
-    toml::from_str(&contents).unwrap_or_default()
+    let contents = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Error reading config file '{}': {e}", config_path.display()))?;
+    parse_config(&contents)
+        .map_err(|e| format!("Error in config file '{}': {e}", config_path.display()))
+}
+
+pub fn parse_config(contents: &str) -> Result<Config, String> {
+    toml::from_str(contents).map_err(|e| e.to_string())
 }
 
 pub fn parse_config_from_str(contents: &str) -> Config {
@@ -74,6 +194,7 @@ pub fn determine_action(filename: &str, append: bool) -> &'static str {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
     use tempfile::NamedTempFile;
@@ -84,6 +205,11 @@ mod tests {
         assert_eq!(config.ask_confirmation, false);
         assert_eq!(config.quiet, false);
         assert_eq!(config.detect_type, true);
+        assert_eq!(config.detect_max_bytes, DEFAULT_DETECT_MAX_BYTES);
+        assert_eq!(config.on_secret, "warn");
+        assert_eq!(config.on_type_mismatch, "warn");
+        assert_eq!(config.html_to_markdown, true);
+        assert_eq!(config.sanitize_code, false);
     }
 
     #[test]
@@ -92,11 +218,29 @@ mod tests {
             ask_confirmation = true
             quiet = true
             detect_type = false
+            detect_max_bytes = 0
+            on_secret = "refuse"
+            on_type_mismatch = "fail"
+            html_to_markdown = false
+            sanitize_code = true
+            file_mode = "600"
+            encrypt = true
+            recipients = ["age1abc"]
+            history = true
         "#;
         let config = parse_config_from_str(toml_str);
         assert_eq!(config.ask_confirmation, true);
         assert_eq!(config.quiet, true);
         assert_eq!(config.detect_type, false);
+        assert_eq!(config.detect_max_bytes, 0);
+        assert_eq!(config.on_secret, "refuse");
+        assert_eq!(config.on_type_mismatch, "fail");
+        assert_eq!(config.html_to_markdown, false);
+        assert_eq!(config.sanitize_code, true);
+        assert_eq!(config.file_mode.as_deref(), Some("600"));
+        assert_eq!(config.encrypt, true);
+        assert_eq!(config.recipients, vec!["age1abc"]);
+        assert_eq!(config.history, true);
     }
 
     #[test]
@@ -122,6 +266,27 @@ mod tests {
         assert_eq!(config, Config::default());
     }
 
+    #[test]
+    fn test_parse_config_errors() {
+        assert!(parse_config("encrypt = true").unwrap().encrypt);
+        // a typo anywhere must not quietly turn encryption off
+        let err = parse_config("encrypt = true\nquiet = ture").unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+        assert!(parse_config("encrypt = \"yes\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_config_errors_keep_secret_settings() {
+        // an unquoted mode would otherwise drop on_secret and file_mode too
+        let err = parse_config("on_secret = \"refuse\"\nfile_mode = 600").unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+        let err = parse_config("on_secret = refuse").unwrap_err();
+        assert!(err.contains("line 1"), "{err}");
+        let config = parse_config("on_secret = \"refuse\"\nfile_mode = \"600\"").unwrap();
+        assert_eq!(config.on_secret, "refuse");
+        assert_eq!(config.file_mode.as_deref(), Some("600"));
+    }
+
     #[test]
     fn test_determine_action_create() {
         let action = determine_action("nonexistent_file.txt", false);
@@ -144,12 +309,79 @@ mod tests {
         assert_eq!(action, "overwrite");
     }
 
+    #[test]
+    fn test_parse_text_output_config() {
+        let toml_str = r#"
+            eol = "lf"
+            bom = "strip"
+
+            [types.ps1]
+            eol = "crlf"
+            bom = "add"
+            encoding = "utf-16le"
+        "#;
+        let config = parse_config_from_str(toml_str);
+        assert_eq!(config.eol.as_deref(), Some("lf"));
+        assert_eq!(config.bom.as_deref(), Some("strip"));
+        assert_eq!(config.encoding, None);
+
+        let ps1 = &config.types["ps1"];
+        assert_eq!(ps1.eol.as_deref(), Some("crlf"));
+        assert_eq!(ps1.bom.as_deref(), Some("add"));
+        assert_eq!(ps1.encoding.as_deref(), Some("utf-16le"));
+    }
+
+    #[test]
+    fn test_parse_custom_types() {
+        let toml_str = r#"
+            [[custom_types]]
+            name = "Terraform"
+            extension = "tf"
+            priority = 10
+            regex = '^\s*(resource|variable|provider) "'
+            keywords = ["resource", "variable", "output"]
+            min_keywords = 2
+
+            [[custom_types]]
+            name = "Nix"
+            extension = "nix"
+            shebang = "nix-shell"
+        "#;
+        let config = parse_config_from_str(toml_str);
+        assert_eq!(config.custom_types.len(), 2);
+
+        let terraform = &config.custom_types[0];
+        assert_eq!(terraform.name, "Terraform");
+        assert_eq!(terraform.extension, "tf");
+        assert_eq!(terraform.priority, 10);
+        assert!(terraform.regex.is_some());
+        assert_eq!(terraform.keywords.len(), 3);
+        assert_eq!(terraform.min_keywords, Some(2));
+        assert!(!terraform.json);
+
+        let nix = &config.custom_types[1];
+        assert_eq!(nix.priority, 0);
+        assert_eq!(nix.shebang.as_deref(), Some("nix-shell"));
+
+        let toml_string = toml::to_string(&config).unwrap();
+        assert_eq!(parse_config_from_str(&toml_string), config);
+    }
+
+    #[test]
+    fn test_default_config_omits_unset_keys() {
+        let toml_string = toml::to_string(&Config::default()).unwrap();
+        assert!(!toml_string.contains("eol"));
+        assert!(!toml_string.contains("[types"));
+        assert!(!toml_string.contains("custom_types"));
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config {
             ask_confirmation: true,
             quiet: true,
             detect_type: true,
+            ..Default::default()
         };
 
         let toml_string = toml::to_string(&config).unwrap();
@@ -170,4 +402,10 @@ mod tests {
         #[cfg(windows)]
         assert!(path_str.contains(".config\\c2f\\config.toml"));
     }
+
+    #[test]
+    fn test_history_dir() {
+        let path = get_history_dir();
+        assert!(path.ends_with(".local/share/c2f"));
+    }
 }
@@ -2,14 +2,50 @@
 // Licensed under the MIT License
 
 mod args;
+mod bundle;
+mod classifier;
+mod completions;
+mod compress;
+mod convert;
+mod crypt;
+mod custom;
 mod detect;
+mod encoding;
+mod fences;
+mod history;
+mod html;
+mod mismatch;
+mod output;
+mod patch;
+mod sanitize;
+mod scan;
+mod secrets;
+mod table;
+mod tokens;
+mod validate;
+mod yaml;
 
 use arboard::Clipboard;
-use c2f::{determine_action, load_config};
-use detect::{detect_content, ClipboardContent, ContentType};
+use args::{Args, CodeBlocks, Command, ConfigCommand, HistoryCommand, Validation};
+use c2f::{determine_action, get_config_path, get_history_dir, load_config, Config};
+use compress::Compression;
+use custom::CustomDetector;
+use detect::{
+    detect_bytes, detect_content, detect_text_type_with, png_to_image, ClipboardContent,
+    ContentType, DetectOptions,
+};
+use encoding::{write_text, Bom, TextOptions};
+use mismatch::MismatchPolicy;
+use output::Finish;
+use secrets::SecretPolicy;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Raw image size from which saving reports its progress.
+const PROGRESS_MIN_BYTES: usize = 32 * 1024 * 1024;
 
 fn ask_confirmation(filename: &str, append: bool) -> bool {
     let action = determine_action(filename, append);
@@ -22,82 +58,824 @@ fn ask_confirmation(filename: &str, append: bool) -> bool {
     input.trim().to_lowercase() == "y"
 }
 
-fn main() {
-    let args = match args::Args::parse() {
-        Ok(args) => args,
-        Err(err) => {
-            match err {
-                args::ArgsError::Help => args::print_help(),
-                args::ArgsError::Version => args::print_version(),
-                _ => args::print_error(&err),
+/// Applies the secret policy to text content, returning the text to write
+/// and whether any secrets were found.
+fn check_secrets(
+    text: String,
+    policy: SecretPolicy,
+    quiet: bool,
+) -> Result<(String, bool), String> {
+    if policy == SecretPolicy::Ignore {
+        return Ok((text, false));
+    }
+    let findings = secrets::scan(&text);
+    if findings.is_empty() {
+        return Ok((text, false));
+    }
+
+    let summary: Vec<String> = findings
+        .iter()
+        .map(|f| format!("  line {}: {}", f.line, f.kind))
+        .collect();
+    match policy {
+        SecretPolicy::Refuse => Err(format!(
+            "Refusing to write: clipboard appears to contain secrets\n{}",
+            summary.join("\n")
+        )),
+        SecretPolicy::Redact => {
+            if !quiet {
+                eprintln!(
+                    "Redacted {} secret(s):\n{}",
+                    findings.len(),
+                    summary.join("\n")
+                );
             }
-            std::process::exit(err.exit_code());
+            Ok((secrets::redact(&text, &findings), true))
+        }
+        _ => {
+            if !quiet {
+                eprintln!(
+                    "Warning: clipboard appears to contain secrets\n{}",
+                    summary.join("\n")
+                );
+            }
+            Ok((text, true))
         }
+    }
+}
+
+/// Replaces typography that breaks code, reporting what changed, and warns
+/// about bidirectional control characters, which are left in place.
+fn sanitize_text(text: String, quiet: bool) -> String {
+    let (clean, changes) = sanitize::sanitize(&text);
+    let bidi = sanitize::bidi_lines(&clean);
+    if quiet {
+        return clean;
+    }
+    if !changes.is_empty() {
+        let total: usize = changes.iter().map(|(_, count)| count).sum();
+        let summary: Vec<String> = changes
+            .iter()
+            .map(|(kind, count)| format!("  {kind}: {count}"))
+            .collect();
+        eprintln!("Sanitized {total} character(s):\n{}", summary.join("\n"));
+    }
+    if !bidi.is_empty() {
+        let lines: Vec<String> = bidi.iter().map(usize::to_string).collect();
+        eprintln!(
+            "Warning: bidirectional control characters on line(s) {} can make code read differently than it runs",
+            lines.join(", ")
+        );
+    }
+    clean
+}
+
+fn restore_to_clipboard(bytes: Vec<u8>) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(|e| format!("Failed to open clipboard: {e}"))?;
+    let result = if bytes.starts_with(detect::PNG_SIGNATURE) {
+        clipboard.set_image(png_to_image(&bytes)?)
+    } else {
+        let text = String::from_utf8(bytes)
+            .map_err(|_| "Content is not valid UTF-8 text or a PNG image".to_string())?;
+        clipboard.set_text(text)
     };
+    result.map_err(|e| format!("Failed to write clipboard: {e}"))
+}
 
-    let append = args.append;
-    let quiet_flag = args.quiet;
-    let config = load_config();
-    let quiet = quiet_flag || config.quiet;
+fn run_decrypt(args: &Args, identity_file: Option<&str>, quiet: bool) -> Result<(), String> {
+    let filename = args
+        .filename
+        .as_ref()
+        .ok_or_else(|| "--decrypt requires a filename".to_string())?;
 
-    // Get clipboard contents and detect type
-    let mut clipboard = Clipboard::new().expect("Failed to initialize clipboard");
-    // Use explicit --detect flag if provided, otherwise use config setting
-    let should_detect = args.detect.unwrap_or(config.detect_type);
+    let ciphertext = fs::read(filename).map_err(|e| format!("Error reading '{filename}': {e}"))?;
+    let mut plaintext = crypt::decrypt(&ciphertext, identity_file)?;
+    // saving compresses before it encrypts, e.g. into "logs.txt.gz.age"
+    let inner = filename.strip_suffix(".age").unwrap_or(filename);
+    let compression = Compression::split_filename(inner)
+        .map(|(_, compression)| compression)
+        .or_else(|| Compression::sniff(&plaintext));
+    if let Some(compression) = compression {
+        plaintext = compress::decompress(&plaintext, compression)?;
+    }
+    if args.stdout {
+        return io::stdout()
+            .write_all(&plaintext)
+            .map_err(|e| format!("Error writing to stdout: {e}"));
+    }
 
-    let (content_type, clipboard_content) = if !should_detect {
-        // Skip detection, treat as plain text
-        let text = clipboard
-            .get_text()
-            .map_err(|e| format!("Error reading clipboard: {e}"))
-            .unwrap_or_else(|e| {
-                eprintln!("{e}");
-                std::process::exit(1);
-            });
+    restore_to_clipboard(plaintext)?;
+    if !quiet {
+        println!("Restored '{filename}' to clipboard");
+    }
+    Ok(())
+}
 
-        if text.is_empty() {
-            eprintln!("Clipboard is empty");
-            std::process::exit(1);
+fn run_history(command: &HistoryCommand, quiet: bool) -> Result<(), String> {
+    let history = history::History::open(get_history_dir());
+    let print_entries = |entries: Vec<history::Entry>| {
+        for entry in entries {
+            println!(
+                "{:>4}  {}  {:<16} {:>9}  {}",
+                entry.id,
+                history::format_timestamp(entry.timestamp),
+                entry.content_type,
+                history::format_size(entry.size),
+                entry.path
+            );
         }
+    };
 
-        (ContentType::PlainText, ClipboardContent::Text(text))
-    } else {
-        match detect_content(&mut clipboard) {
-            Ok(result) => result,
+    match command {
+        HistoryCommand::List => print_entries(history.entries()?),
+        HistoryCommand::Search(term) => print_entries(history.search(term)?),
+        HistoryCommand::Restore(id) => {
+            restore_to_clipboard(history.content(*id)?)?;
+            if !quiet {
+                println!("Restored history entry {id} to clipboard");
+            }
+        }
+        HistoryCommand::Prune(age) => {
+            let removed = history.prune(*age)?;
+            if !quiet {
+                println!("Removed {removed} history entries");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_config(command: &ConfigCommand, config: &Config) -> Result<(), String> {
+    match command {
+        ConfigCommand::Path => println!("{}", get_config_path().display()),
+        ConfigCommand::Show => {
+            let toml_string = toml::to_string(config).map_err(|e| e.to_string())?;
+            print!("{toml_string}");
+        }
+    }
+    Ok(())
+}
+
+fn run_paste() -> Result<(), String> {
+    let mut clipboard =
+        Clipboard::new().map_err(|e| format!("Failed to initialize clipboard: {e}"))?;
+    let mut stdout = io::stdout().lock();
+    let result = match detect_content(&mut clipboard, &DetectOptions::default())?.1 {
+        ClipboardContent::Image(img) => detect::write_png(&img, &mut stdout, &mut |_, _| {}),
+        ClipboardContent::Text(text) => stdout.write_all(text.as_bytes()),
+    };
+    result
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("Error writing to stdout: {e}"))
+}
+
+fn run_detect(paths: &[String], json: bool, options: &DetectOptions) -> Result<(), String> {
+    // `-` or no paths at all means stdin
+    let stdin = [String::from("-")];
+    let paths = if paths.is_empty() { &stdin[..] } else { paths };
+
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for path in paths {
+        let bytes = if path == "-" {
+            let mut bytes = Vec::new();
+            io::stdin()
+                .read_to_end(&mut bytes)
+                .map(|_| bytes)
+                .map_err(|e| format!("Error reading stdin: {e}"))
+        } else {
+            fs::read(path).map_err(|e| format!("Error reading '{path}': {e}"))
+        };
+        match bytes {
+            Ok(bytes) => results.push((path, detect_bytes(&bytes, options))),
             Err(e) => {
                 eprintln!("{e}");
-                std::process::exit(1);
+                failed += 1;
             }
         }
+    }
+
+    if json {
+        #[derive(serde::Serialize)]
+        struct Detection<'a> {
+            path: Option<&'a str>,
+            #[serde(rename = "type")]
+            content_type: String,
+            extension: &'a str,
+        }
+
+        let entries: Vec<Detection> = results
+            .iter()
+            .map(|(path, content_type)| Detection {
+                path: Some(path.as_str()).filter(|path| *path != "-"),
+                content_type: content_type.to_string(),
+                extension: content_type.extension(),
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+        println!("{output}");
+    } else if paths.len() == 1 {
+        for (_, content_type) in &results {
+            println!("{content_type} ({})", content_type.extension());
+        }
+    } else {
+        for (path, content_type) in &results {
+            println!("{path}: {content_type} ({})", content_type.extension());
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("Could not read {failed} of {} inputs", paths.len()));
+    }
+    Ok(())
+}
+
+fn read_clipboard(
+    clipboard: &mut Clipboard,
+    should_detect: bool,
+    forced_type: Option<&ContentType>,
+    options: &DetectOptions,
+) -> Result<(ContentType, ClipboardContent), String> {
+    match forced_type {
+        Some(ContentType::Image) => {
+            let img = clipboard
+                .get_image()
+                .map_err(|_| "Clipboard does not contain an image".to_string())?;
+            return Ok((ContentType::Image, ClipboardContent::Image(img)));
+        }
+        Some(content_type) => {
+            let text = clipboard
+                .get_text()
+                .map_err(|e| format!("Error reading clipboard: {e}"))?;
+            if text.is_empty() {
+                return Err("Clipboard is empty".to_string());
+            }
+            return Ok((content_type.clone(), ClipboardContent::Text(text)));
+        }
+        None => {}
+    }
+    if should_detect {
+        return detect_content(clipboard, options);
+    }
+
+    // Skip detection, treat as plain text
+    let text = clipboard
+        .get_text()
+        .map_err(|e| format!("Error reading clipboard: {e}"))?;
+    if text.is_empty() {
+        return Err("Clipboard is empty".to_string());
+    }
+    Ok((ContentType::PlainText, ClipboardContent::Text(text)))
+}
+
+fn run_save(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    options: &DetectOptions,
+) -> Result<(), String> {
+    if args.decrypt {
+        let identity_file = args.identity.as_deref().or(config.identity_file.as_deref());
+        return run_decrypt(args, identity_file, quiet);
+    }
+
+    // Get clipboard contents and detect type
+    let mut clipboard =
+        Clipboard::new().map_err(|e| format!("Failed to initialize clipboard: {e}"))?;
+    // Use explicit --detect flag if provided, otherwise use config setting;
+    // --apply always needs to know whether it's looking at a diff, and
+    // --convert what it converts from; with --extract-code each block is
+    // detected instead
+    let should_detect = args.extract_code.is_none()
+        && (args.apply || args.convert || args.detect.unwrap_or(config.detect_type));
+    let (content_type, clipboard_content) = read_clipboard(
+        &mut clipboard,
+        should_detect,
+        args.content_type.as_ref(),
+        options,
+    )?;
+
+    if args.apply {
+        return run_apply(content_type, clipboard_content, quiet);
+    }
+    if let Some(which) = args.extract_code {
+        return run_extract(args, config, quiet, options, which, clipboard_content);
+    }
+    if args.split {
+        return run_split(args, config, quiet, options, clipboard_content);
+    }
+    if args.convert {
+        return run_convert(args, config, quiet, content_type, clipboard_content);
+    }
+    if let Some(markdown) =
+        html_as_markdown(&mut clipboard, args, config, options, &clipboard_content)
+    {
+        if !quiet {
+            println!("Converted HTML to Markdown");
+        }
+        let content = ClipboardContent::Text(markdown);
+        return save(args, config, quiet, ContentType::Markdown, content);
+    }
+    // an explicit --type says what the content is
+    if should_detect
+        && args.content_type.is_none()
+        && !check_type_mismatch(args, config, quiet, &content_type)?
+    {
+        return Ok(());
+    }
+    save(args, config, quiet, content_type, clipboard_content)
+}
+
+/// Compares the type named by an explicit filename's extension with the
+/// detected one, and acts on a mismatch as `on_type_mismatch` says. `false`
+/// when the save was cancelled at the prompt.
+fn check_type_mismatch(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    detected: &ContentType,
+) -> Result<bool, String> {
+    let policy = MismatchPolicy::parse(&config.on_type_mismatch).ok_or_else(|| {
+        format!(
+            "Invalid value '{}' for 'on_type_mismatch' in config file (expected one of: {})",
+            config.on_type_mismatch,
+            MismatchPolicy::VALUES.join(", ")
+        )
+    })?;
+    let Some(named) = filename_type(args) else {
+        return Ok(true);
     };
+    if policy == MismatchPolicy::Ignore || !mismatch::is_mismatch(&named, detected) {
+        return Ok(true);
+    }
 
-    // Determine filename
-    let filename = if args.has_explicit_filename() {
-        let base_filename = args.filename().to_string();
+    let mut message = format!(
+        "clipboard looks like {detected}, but '{}' is named as {named}",
+        args.filename()
+    );
+    let convertible = |t: &ContentType| convert::data_format(t).is_some();
+    if (convertible(detected) && convertible(&named))
+        || (table::is_table_format(detected) && table::is_table_format(&named))
+    {
+        message.push_str(" (--convert converts it)");
+    }
+    match policy {
+        MismatchPolicy::Fail => Err(format!("Refusing to write: {message}")),
+        MismatchPolicy::Prompt => {
+            print!("Warning: {message}. Save anyway? (y/n): ");
+            io::stdout().flush().unwrap();
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).unwrap();
+            let save = input.trim().to_lowercase() == "y";
+            if !save && !quiet {
+                println!("Cancelled.");
+            }
+            Ok(save)
+        }
+        _ => {
+            if !quiet {
+                eprintln!("Warning: {message}");
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Markdown for a save to a .md file (or `--type md`) when the clipboard
+/// has HTML: the rich text flavour of a browser copy, or HTML source.
+fn html_as_markdown(
+    clipboard: &mut Clipboard,
+    args: &Args,
+    config: &Config,
+    options: &DetectOptions,
+    clipboard_content: &ClipboardContent,
+) -> Option<String> {
+    let text = match clipboard_content {
+        ClipboardContent::Text(text) => text,
+        ClipboardContent::Image(_) => return None,
+    };
+    let to_markdown = args.content_type == Some(ContentType::Markdown)
+        || filename_type(args) == Some(ContentType::Markdown);
+    if !config.html_to_markdown || !to_markdown {
+        return None;
+    }
+
+    // copying Markdown source in an editor puts highlighted HTML next to it
+    let detected = detect_text_type_with(text, options);
+    if detected == ContentType::Markdown {
+        return None;
+    }
+    let html = clipboard
+        .get()
+        .html()
+        .ok()
+        .filter(|html| !html.trim().is_empty() && !html::is_styled_source(html))
+        .or_else(|| (detected == ContentType::Html).then(|| text.clone()))?;
+    Some(html::to_markdown(&html)).filter(|markdown| !markdown.is_empty())
+}
 
+/// The type an explicit filename asks for by its extension, looking past
+/// .gz, .zst and .age suffixes.
+fn filename_type(args: &Args) -> Option<ContentType> {
+    if !args.has_explicit_filename() {
+        return None;
+    }
+    let name = args.filename();
+    let name = name
+        .strip_suffix(&format!(".{}", crypt::EXTENSION))
+        .unwrap_or(name);
+    let name = Compression::split_filename(name).map_or(name, |(stem, _)| stem);
+    let extension = Path::new(name).extension()?.to_str()?;
+    ContentType::from_name(extension)
+}
+
+/// Saves JSON, YAML or TOML on the clipboard in the data format implied by
+/// the extension of [filename].
+fn run_convert(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    content_type: ContentType,
+    clipboard_content: ClipboardContent,
+) -> Result<(), String> {
+    let ClipboardContent::Text(text) = clipboard_content else {
+        return Err("--convert needs text on the clipboard".to_string());
+    };
+    let named = filename_type(args);
+    // tables go between CSV, TSV, Markdown and JSON
+    if let Some(target) = named.as_ref() {
+        if let Some(converted) = table::convert(&text, &content_type, target) {
+            if args.append {
+                return Err(
+                    "Tables aren't converted when appending, the rows would bring their own header"
+                        .to_string(),
+                );
+            }
+            if !quiet {
+                println!("Converted {content_type} to {target}");
+            }
+            return save(
+                args,
+                config,
+                quiet,
+                target.clone(),
+                ClipboardContent::Text(converted),
+            );
+        }
+    }
+    let target = named
+        .filter(|t| convert::data_format(t).is_some())
+        .ok_or("--convert needs a .json, .yaml, .toml, .csv, .tsv or .md filename to convert to")?;
+    if convert::data_format(&content_type) == convert::data_format(&target) {
+        return save(
+            args,
+            config,
+            quiet,
+            content_type,
+            ClipboardContent::Text(text),
+        );
+    }
+    let converted = convert::convert(&text, &content_type, &target)?;
+    if !quiet {
+        println!("Converted {content_type} to {target}");
+    }
+    save(
+        args,
+        config,
+        quiet,
+        target,
+        ClipboardContent::Text(converted),
+    )
+}
+
+/// Saves the fenced code blocks of Markdown on the clipboard, each typed by
+/// its info string or by detection, the same way a clipboard is saved.
+fn run_extract(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    options: &DetectOptions,
+    which: CodeBlocks,
+    clipboard_content: ClipboardContent,
+) -> Result<(), String> {
+    let text = match clipboard_content {
+        ClipboardContent::Text(text) => text,
+        ClipboardContent::Image(_) => {
+            return Err("--extract-code needs Markdown text on the clipboard".to_string())
+        }
+    };
+    let blocks = fences::code_blocks(&text);
+    if blocks.is_empty() {
+        return Err("No fenced code blocks found on the clipboard".to_string());
+    }
+    let selected: Vec<_> = match which {
+        CodeBlocks::All => blocks.iter().enumerate().map(|(i, b)| (i + 1, b)).collect(),
+        CodeBlocks::Only(n) => {
+            let block = blocks
+                .get(n - 1)
+                .ok_or_else(|| format!("There is no code block {n}, found {}", blocks.len()))?;
+            vec![(n, block)]
+        }
+    };
+    let numbered = selected.len() > 1;
+
+    for (n, block) in selected {
+        let content_type = args
+            .content_type
+            .clone()
+            .unwrap_or_else(|| block.content_type(options));
+        let mut block_args = args.clone();
+        // the block types are known, so name files after them
+        block_args.detect = Some(true);
+        if let (true, Some(name)) = (numbered, &args.filename) {
+            let name = fences::numbered_filename(name, n);
+            let file_name = name.rsplit('/').next().unwrap_or_default();
+            // out -> out-1.rs, out-2.py; snippet.py -> snippet-1.py
+            block_args.append_ext |= !file_name.trim_start_matches('.').contains('.');
+            block_args.filename = Some(name);
+        }
+        let content = ClipboardContent::Text(block.body.clone());
+        save(&block_args, config, quiet, content_type, content).map_err(|e| {
+            if numbered {
+                format!("Code block {n}: {e}")
+            } else {
+                e
+            }
+        })?;
+    }
+    Ok(())
+}
+
+fn run_apply(
+    content_type: ContentType,
+    clipboard_content: ClipboardContent,
+    quiet: bool,
+) -> Result<(), String> {
+    let patch = match (content_type, clipboard_content) {
+        (ContentType::Diff, ClipboardContent::Text(text)) => text,
+        (content_type, _) => {
+            return Err(format!(
+                "--apply needs a diff on the clipboard, found: {content_type}"
+            ))
+        }
+    };
+
+    let dir =
+        std::env::current_dir().map_err(|e| format!("Error reading current directory: {e}"))?;
+    let root = patch::apply(&patch, &dir)?;
+    if !quiet {
+        println!("Applied patch to '{}'", root.display());
+    }
+    Ok(())
+}
+
+/// Writes each file of a bundle on the clipboard to the path in its header,
+/// under the directory given as filename. Every path is checked before
+/// anything is written.
+fn run_split(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    options: &DetectOptions,
+    clipboard_content: ClipboardContent,
+) -> Result<(), String> {
+    let text = match clipboard_content {
+        ClipboardContent::Text(text) => text,
+        ClipboardContent::Image(_) => return Err("--split needs text on the clipboard".to_string()),
+    };
+    let files = bundle::split(&text);
+    if files.is_empty() {
+        return Err(
+            "No file headers found on the clipboard, e.g. '// file: src/main.rs' or '==> a.txt <=='"
+                .to_string(),
+        );
+    }
+
+    let base = Path::new(args.filename.as_deref().unwrap_or("."));
+    fs::create_dir_all(base)
+        .map_err(|e| format!("Error creating directory '{}': {e}", base.display()))?;
+
+    let mut targets = Vec::new();
+    let mut existing = Vec::new();
+    for file in &files {
+        let relative = bundle::relative_path(&file.path)?;
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // the extension decides the type; without one, the content does
+        let named = match Path::new(&name).extension() {
+            Some(ext) => ContentType::from_name(&ext.to_string_lossy()),
+            None => ContentType::from_name(&name),
+        };
+        let content_type = args
+            .content_type
+            .clone()
+            .or_else(|| named.clone())
+            .unwrap_or_else(|| detect_text_type_with(&file.content, options));
+
+        let mut target = base.join(&relative);
+        // Makefile, LICENSE and .gitignore keep their names
+        if !name.contains('.') && named.is_none() && content_type != ContentType::PlainText {
+            target.set_extension(content_type.extension());
+        }
+        if targets.iter().any(|(t, _, _)| *t == target) {
+            return Err(format!("'{}' appears more than once", target.display()));
+        }
+        bundle::check_inside(base, &target)?;
+        if !args.append && target.exists() {
+            existing.push(target.display().to_string());
+        }
+        targets.push((target, content_type, file));
+    }
+    if !existing.is_empty() && !args.force {
+        return Err(format!(
+            "Refusing to overwrite existing files (use --force): {}",
+            existing.join(", ")
+        ));
+    }
+
+    for (target, content_type, file) in targets {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Error creating directory '{}': {e}", parent.display()))?;
+        }
+        let mut file_args = args.clone();
+        file_args.filename = Some(target.to_string_lossy().to_string());
+        file_args.append_ext = false;
+        let content = ClipboardContent::Text(file.content.clone());
+        save(&file_args, config, quiet, content_type, content)
+            .map_err(|e| format!("{}: {e}", file.path))?;
+    }
+    Ok(())
+}
+
+/// Cheap identity of the current clipboard contents, used by watch to notice
+/// changes without encoding images.
+fn clipboard_fingerprint(clipboard: &mut Clipboard) -> Option<String> {
+    if let Ok(img) = clipboard.get_image() {
+        return Some(history::hash(&img.bytes));
+    }
+    clipboard
+        .get_text()
+        .ok()
+        .filter(|text| !text.is_empty())
+        .map(|text| history::hash(text.as_bytes()))
+}
+
+fn run_watch(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    options: &DetectOptions,
+) -> Result<(), String> {
+    let mut clipboard =
+        Clipboard::new().map_err(|e| format!("Failed to initialize clipboard: {e}"))?;
+    let should_detect = args.detect.unwrap_or(config.detect_type);
+    let interval = args.interval.unwrap_or(Duration::from_secs(1));
+
+    // only save what gets copied from now on
+    let mut last = clipboard_fingerprint(&mut clipboard);
+    if !quiet {
+        println!("Watching clipboard, press Ctrl-C to stop");
+    }
+
+    loop {
+        thread::sleep(interval);
+        let current = clipboard_fingerprint(&mut clipboard);
+        if current.is_none() || current == last {
+            continue;
+        }
+        last = current;
+
+        let result = read_clipboard(
+            &mut clipboard,
+            should_detect,
+            args.content_type.as_ref(),
+            options,
+        )
+        .and_then(|(content_type, content)| save(args, config, quiet, content_type, content));
+        if let Err(e) = result {
+            eprintln!("{e}");
+        }
+    }
+}
+
+/// Writes one clipboard capture according to the flags and config.
+fn save(
+    args: &Args,
+    config: &Config,
+    quiet: bool,
+    content_type: ContentType,
+    clipboard_content: ClipboardContent,
+) -> Result<(), String> {
+    let append = args.append;
+    // an explicit --type counts as detection for naming purposes
+    let should_detect = args.content_type.is_some() || args.detect.unwrap_or(config.detect_type);
+
+    let encrypt = args.encrypt || !args.recipients.is_empty() || config.encrypt;
+    if encrypt && append {
+        return Err("Cannot append to encrypted files".to_string());
+    }
+
+    let clipboard_content = match clipboard_content {
+        ClipboardContent::Text(text)
+            if args.sanitize || (config.sanitize_code && content_type.is_code()) =>
+        {
+            ClipboardContent::Text(sanitize_text(text, quiet))
+        }
+        other => other,
+    };
+
+    if let (Some(validation), ClipboardContent::Text(text)) = (args.validate, &clipboard_content) {
+        // broken data often isn't detected as what it was meant to be, so
+        // the type named by --type or the filename comes first
+        let format = args
+            .content_type
+            .clone()
+            .or_else(|| filename_type(args))
+            .unwrap_or_else(|| content_type.clone());
+        if let Some(Err(e)) = validate::check(text, &format) {
+            let message = format!("clipboard isn't valid {format}: {e}");
+            if validation == Validation::Fail {
+                return Err(format!("Refusing to write: {message}"));
+            }
+            if !quiet {
+                eprintln!("Warning: {message}");
+            }
+        }
+    }
+
+    let secret_policy = if args.redact {
+        SecretPolicy::Redact
+    } else if encrypt && SecretPolicy::parse(&config.on_secret) == Some(SecretPolicy::Warn) {
+        // secrets are fine on disk once encrypted
+        SecretPolicy::Ignore
+    } else {
+        SecretPolicy::parse(&config.on_secret).ok_or_else(|| {
+            format!(
+                "Invalid value '{}' for 'on_secret' in config file (expected one of: {})",
+                config.on_secret,
+                SecretPolicy::VALUES.join(", ")
+            )
+        })?
+    };
+
+    // Compression comes from --compress or a .gz/.zst suffix on the filename
+    let mut compression = args.compress;
+    let explicit_filename = args.has_explicit_filename().then(|| {
+        let name = args.filename();
+        let name = if encrypt {
+            name.strip_suffix(&format!(".{}", crypt::EXTENSION))
+                .unwrap_or(name)
+        } else {
+            name
+        };
+        match Compression::split_filename(name) {
+            Some((stem, from_name)) => {
+                compression = compression.or(Some(from_name));
+                stem
+            }
+            None => name,
+        }
+    });
+
+    // Suffixes added after the content extension, e.g. ".gz.age"
+    let mut suffix = String::new();
+    if let Some(compression) = compression {
+        suffix.push_str(&format!(".{}", compression.extension()));
+    }
+    if encrypt {
+        suffix.push_str(&format!(".{}", crypt::EXTENSION));
+    }
+
+    // Determine filename
+    let filename = if let Some(base_filename) = explicit_filename {
         // If --append-ext is used and detection is enabled, append the extension
         if args.append_ext && should_detect {
             let extension = content_type.extension();
             // Only append extension if the filename doesn't already have it
             if !base_filename.ends_with(&format!(".{extension}")) {
-                format!("{base_filename}.{extension}")
+                format!("{base_filename}.{extension}{suffix}")
             } else {
-                base_filename
+                format!("{base_filename}{suffix}")
             }
         } else {
-            base_filename
+            format!("{base_filename}{suffix}")
         }
     } else {
         // Generate filename with detected extension
-        let base_name = "clipboard";
+        let base_name = content_type
+            .file_stem()
+            .unwrap_or_else(|| "clipboard".to_string());
         let extension = content_type.extension();
-        let mut filename = format!("{base_name}.{extension}");
+        let mut filename = format!("{base_name}.{extension}{suffix}");
 
         // If file exists and not appending, add number suffix
         if !append && Path::new(&filename).exists() {
             let mut counter = 2;
             loop {
-                filename = format!("{base_name}-{counter}.{extension}");
+                filename = format!("{base_name}-{counter}.{extension}{suffix}");
                 if !Path::new(&filename).exists() {
                     break;
                 }
@@ -111,43 +889,196 @@ fn main() {
         if !quiet {
             println!("Cancelled.");
         }
-        return;
+        return Ok(());
     }
 
-    // Get bytes to write
-    let bytes = match clipboard_content {
-        ClipboardContent::Image(bytes) => bytes,
-        ClipboardContent::Text(text) => text.into_bytes(),
+    let config_mode = match config.file_mode.as_deref() {
+        Some(value) => Some(
+            output::parse_mode(value)
+                .ok_or_else(|| format!("Invalid value '{value}' for 'file_mode' in config file"))?,
+        ),
+        None => None,
     };
+    let mut sensitive = matches!(content_type, ContentType::DotEnv);
+
+    // Only append for text files
+    if append && matches!(content_type, ContentType::Image) {
+        return Err("Cannot append to image files".to_string());
+    }
+
+    // Everything that can fail is settled before the file is opened, since
+    // the content is streamed into it
+    let mut text_options = TextOptions::default();
+    let clipboard_content = match clipboard_content {
+        ClipboardContent::Text(text) => {
+            let (mut text, has_secrets) = check_secrets(text, secret_policy, quiet)?;
+            if content_type == ContentType::Diff {
+                // keep saved patches applicable
+                text = patch::with_trailing_newline(&text);
+            }
+            sensitive |= has_secrets;
 
-    let result = if append {
-        // Only append for text files
-        if matches!(content_type, ContentType::Image) {
-            eprintln!("Cannot append to image files");
-            std::process::exit(1);
+            let mut options = TextOptions::from_config(config, &content_type)?;
+            options.eol = args.eol.unwrap_or(options.eol);
+            options.bom = args.bom.unwrap_or(options.bom);
+            options.encoding = args.encoding.unwrap_or(options.encoding);
+            options.check()?;
+
+            // a BOM only belongs at the start of a file
+            let has_content = fs::metadata(&filename).map_or(false, |m| m.len() > 0);
+            if append && has_content {
+                options.bom = Bom::Strip;
+            }
+            encoding::check_encodable(&text, &options)?;
+            text_options = options;
+            ClipboardContent::Text(text)
         }
-        fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&filename)
-            .and_then(|mut file| file.write_all(&bytes))
+        image => image,
+    };
+
+    let key = if encrypt {
+        let recipients = if args.recipients.is_empty() {
+            &config.recipients
+        } else {
+            &args.recipients
+        };
+        Some(crypt::EncryptKey::resolve(recipients)?)
     } else {
-        fs::write(&filename, &bytes)
+        None
     };
 
-    match result {
-        Ok(_) => {
-            if !quiet {
-                let action = if append { "appended to" } else { "written to" };
-                if should_detect && !args.has_explicit_filename() {
-                    println!("Detected format: {content_type}");
+    if append {
+        output::check_appendable(Path::new(&filename))?;
+    }
+    let mode = output::resolve_mode(args.mode, config_mode, sensitive);
+    let file = output::open_output(Path::new(&filename), append, mode)
+        .map_err(|e| format!("Error writing to file: {e}"))?;
+
+    // content -> compression -> encryption -> file
+    let mut writer: Box<dyn Finish> = Box::new(io::BufWriter::new(file));
+    if let Some(key) = &key {
+        writer = crypt::encryptor(writer, key)?;
+    }
+    if let Some(compression) = compression {
+        writer = compress::encoder(writer, compression)?;
+    }
+
+    let mut image_copy = None;
+    let result = match &clipboard_content {
+        ClipboardContent::Text(text) => write_text(&mut writer, text, &text_options),
+        ClipboardContent::Image(img) => {
+            let show_progress = !quiet && img.bytes.len() >= PROGRESS_MIN_BYTES;
+            let mut progress = |done: usize, total: usize| {
+                if show_progress {
+                    eprint!("\rEncoding image: {:>3}%", done * 100 / total);
+                    if done == total {
+                        eprintln!();
+                    }
                 }
-                println!("Successfully {action} '{filename}'");
+            };
+            if config.history {
+                // history keeps the PNG, so encode it once into memory
+                let mut png = Vec::new();
+                let result = detect::write_png(img, &mut png, &mut progress)
+                    .and_then(|_| writer.write_all(&png));
+                image_copy = Some(png);
+                result
+            } else {
+                detect::write_png(img, &mut writer, &mut progress)
             }
         }
-        Err(e) => {
-            eprintln!("Error writing to file: {e}");
-            std::process::exit(1);
+    }
+    .and_then(|_| writer.finish());
+
+    if result.is_ok() && config.history {
+        let content = match &clipboard_content {
+            ClipboardContent::Text(text) => Some(text.as_bytes()),
+            ClipboardContent::Image(_) => image_copy.as_deref(),
+        };
+        if let Some(content) = content {
+            // keep a copy for restore unless it shouldn't be on disk in plain text
+            let stored = !(sensitive || encrypt);
+            let entry = history::History::open(get_history_dir()).record(
+                &content_type.to_string(),
+                content.len() as u64,
+                history::hash(content),
+                Path::new(&filename),
+                if stored { Some(content) } else { None },
+            );
+            if let Err(e) = entry {
+                eprintln!("Warning: could not record history: {e}");
+            }
         }
     }
+
+    result.map_err(|e| format!("Error writing to file: {e}"))?;
+    if !quiet {
+        let action = if append { "appended to" } else { "written to" };
+        if args.content_type.is_none() && should_detect && !args.has_explicit_filename() {
+            println!("Detected format: {content_type}");
+        }
+        println!("Successfully {action} '{filename}'");
+    }
+    Ok(())
+}
+
+fn print_types(custom: &[CustomDetector]) {
+    let custom = custom.iter().map(|d| &d.content_type);
+    for content_type in ContentType::ALL.iter().chain(custom) {
+        println!("{:<12}{content_type}", content_type.extension());
+    }
+}
+
+fn main() {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            match err {
+                args::ArgsError::Help => args::print_help(),
+                args::ArgsError::Version => args::print_version(),
+                _ => args::print_error(&err),
+            }
+            std::process::exit(err.exit_code());
+        }
+    };
+
+    let config = load_config().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let quiet = args.quiet || config.quiet;
+    let custom = CustomDetector::compile(&config.custom_types).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    if args.list_types {
+        print_types(&custom);
+        return;
+    }
+    let options = DetectOptions {
+        custom: &custom,
+        max_bytes: config.detect_max_bytes,
+    };
+
+    let result = match &args.command {
+        Command::Save => run_save(&args, &config, quiet, &options),
+        Command::Watch => run_watch(&args, &config, quiet, &options),
+        Command::Paste => run_paste(),
+        Command::Detect(paths) => run_detect(paths, args.json, &options),
+        Command::History(command) => run_history(command, quiet),
+        Command::Config(command) => run_config(command, &config),
+        Command::Completions(shell) => {
+            print!("{}", completions::generate(*shell));
+            Ok(())
+        }
+        Command::Man => {
+            print!("{}", completions::man());
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
 }
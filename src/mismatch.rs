@@ -0,0 +1,68 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Catches accidental pastes: Python saved to out.json, an image to notes.md.
+// The type named by the extension of an explicit filename is compared with
+// the detected one, allowing for content that is valid in the named type
+// anyway, like JavaScript in a .ts file.
+
+use crate::detect::ContentType;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MismatchPolicy {
+    Warn,
+    Prompt,
+    Fail,
+    Ignore,
+}
+
+impl MismatchPolicy {
+    pub const VALUES: &'static [&'static str] = &["warn", "prompt", "fail", "ignore"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "warn" => Some(MismatchPolicy::Warn),
+            "prompt" => Some(MismatchPolicy::Prompt),
+            "fail" => Some(MismatchPolicy::Fail),
+            "ignore" => Some(MismatchPolicy::Ignore),
+            _ => None,
+        }
+    }
+}
+
+/// Whether content detected as `detected` doesn't belong in a file of the
+/// `named` type. Plain text is never a mismatch, since detection wasn't
+/// sure of anything, and .txt and .log files take any text.
+pub fn is_mismatch(named: &ContentType, detected: &ContentType) -> bool {
+    use ContentType::*;
+    if *detected == Image || *named == Image {
+        return detected != named;
+    }
+    if matches!(detected, PlainText) || matches!(named, PlainText | Log) {
+        return false;
+    }
+    if named.extension() == detected.extension() {
+        return false;
+    }
+    // content that is also valid in the named type
+    let accepted: &[ContentType] = match named {
+        TypeScript => &[JavaScript],
+        Jsx => &[JavaScript],
+        Tsx => &[JavaScript, TypeScript, Jsx],
+        Cpp => &[C],
+        Scss => &[Css],
+        Html => &[Svg, Xml],
+        Xml => &[Svg, Html],
+        Svg => &[Xml],
+        Vue | Svelte => &[Html, JavaScript, TypeScript],
+        Json => &[JupyterNotebook],
+        JupyterNotebook => &[Json],
+        Markdown => &[Html],
+        _ => &[],
+    };
+    !accepted.contains(detected)
+}
+
+#[cfg(test)]
+#[path = "mismatch_tests.rs"]
+mod tests;
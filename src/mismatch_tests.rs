@@ -0,0 +1,48 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType::{self, *};
+use crate::mismatch::*;
+
+#[test]
+fn test_mismatch() {
+    assert!(is_mismatch(&Json, &Python));
+    assert!(is_mismatch(&Yaml, &Json));
+    assert!(is_mismatch(&Markdown, &Image));
+    assert!(is_mismatch(&Image, &Rust));
+    assert!(is_mismatch(&JavaScript, &TypeScript));
+}
+
+#[test]
+fn test_compatible() {
+    assert!(!is_mismatch(&Json, &Json));
+    assert!(!is_mismatch(&Image, &Image));
+    // same extension
+    let manifest = ContentType::Kubernetes {
+        kind: "Deployment".to_string(),
+    };
+    assert!(!is_mismatch(&Yaml, &manifest));
+    // valid in the named type anyway
+    assert!(!is_mismatch(&TypeScript, &JavaScript));
+    assert!(!is_mismatch(&Cpp, &C));
+    assert!(!is_mismatch(&Json, &JupyterNotebook));
+    // nothing to compare
+    assert!(!is_mismatch(&Rust, &PlainText));
+    assert!(!is_mismatch(&PlainText, &Python));
+    assert!(!is_mismatch(&Log, &Json));
+}
+
+#[test]
+fn test_policy_parse() {
+    assert_eq!(MismatchPolicy::parse("warn"), Some(MismatchPolicy::Warn));
+    assert_eq!(
+        MismatchPolicy::parse("Prompt"),
+        Some(MismatchPolicy::Prompt)
+    );
+    assert_eq!(MismatchPolicy::parse("fail"), Some(MismatchPolicy::Fail));
+    assert_eq!(
+        MismatchPolicy::parse("ignore"),
+        Some(MismatchPolicy::Ignore)
+    );
+    assert_eq!(MismatchPolicy::parse("refuse"), None);
+}
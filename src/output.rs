@@ -0,0 +1,102 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Mode used for sensitive content when nothing else is configured.
+pub const PRIVATE_MODE: u32 = 0o600;
+
+/// Parses an octal permission string such as `600`, `0600` or `0o600`.
+pub fn parse_mode(value: &str) -> Option<u32> {
+    let digits = value.trim_start_matches("0o");
+    if digits.is_empty() || digits.len() > 4 {
+        return None;
+    }
+    u32::from_str_radix(digits, 8)
+        .ok()
+        .filter(|&mode| mode <= 0o7777)
+}
+
+/// Picks the mode for the output file. An explicit `--mode` always wins;
+/// otherwise sensitive content has group and other bits stripped from the
+/// configured mode, falling back to 0600.
+pub fn resolve_mode(flag: Option<u32>, config: Option<u32>, sensitive: bool) -> Option<u32> {
+    if flag.is_some() {
+        return flag;
+    }
+    if sensitive {
+        return Some(config.unwrap_or(PRIVATE_MODE) & !0o077);
+    }
+    config
+}
+
+/// Refuses to append to files anyone can write to, since their existing
+/// contents can't be trusted.
+pub fn check_appendable(path: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = fs::metadata(path) {
+            if metadata.permissions().mode() & 0o002 != 0 {
+                return Err(format!(
+                    "Refusing to append to world-writable file '{}'",
+                    path.display()
+                ));
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+/// A writer whose end has to be marked: compressors and encryption write a
+/// trailer, buffers flush. `finish` passes the call on to the writer it
+/// wraps, so a whole chain is closed from the outside in.
+pub trait Finish: Write {
+    fn finish(self: Box<Self>) -> io::Result<()>;
+}
+
+impl<W: Write> Finish for io::BufWriter<W> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        self.into_inner().map(drop).map_err(|e| e.into_error())
+    }
+}
+
+/// Opens the output file. New files are created with `mode` (subject to the
+/// umask); existing files are only ever tightened, never loosened.
+pub fn open_output(path: &Path, append: bool, mode: Option<u32>) -> io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.create(true);
+    if append {
+        options.append(true);
+    } else {
+        options.write(true).truncate(true);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        if let Some(mode) = mode {
+            options.mode(mode);
+            if let Ok(metadata) = fs::metadata(path) {
+                let current = metadata.permissions().mode() & 0o7777;
+                if current & mode != current {
+                    fs::set_permissions(path, fs::Permissions::from_mode(current & mode))?;
+                }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    options.open(path)
+}
+
+#[cfg(test)]
+#[path = "output_tests.rs"]
+mod tests;
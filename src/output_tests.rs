@@ -0,0 +1,89 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::output::*;
+use std::io::Write;
+
+#[test]
+fn test_parse_mode() {
+    assert_eq!(parse_mode("600"), Some(0o600));
+    assert_eq!(parse_mode("0644"), Some(0o644));
+    assert_eq!(parse_mode("0o600"), Some(0o600));
+    assert_eq!(parse_mode("800"), None);
+    assert_eq!(parse_mode("rw-------"), None);
+    assert_eq!(parse_mode(""), None);
+    assert_eq!(parse_mode("77777"), None);
+}
+
+#[test]
+fn test_resolve_mode() {
+    // nothing configured
+    assert_eq!(resolve_mode(None, None, false), None);
+    assert_eq!(resolve_mode(None, Some(0o640), false), Some(0o640));
+
+    // sensitive content is tightened
+    assert_eq!(resolve_mode(None, None, true), Some(0o600));
+    assert_eq!(resolve_mode(None, Some(0o644), true), Some(0o600));
+    assert_eq!(resolve_mode(None, Some(0o400), true), Some(0o400));
+
+    // explicit flag wins
+    assert_eq!(resolve_mode(Some(0o644), Some(0o600), true), Some(0o644));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_open_output_creates_with_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secret.env");
+    open_output(&path, false, Some(0o600))
+        .unwrap()
+        .write_all(b"KEY=value")
+        .unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o600);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_open_output_tightens_existing_file() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    fs::write(&path, "old").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    open_output(&path, false, Some(0o600))
+        .unwrap()
+        .write_all(b"new")
+        .unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o600);
+
+    // never loosened
+    open_output(&path, false, Some(0o644)).unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o600);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_appendable() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.txt");
+    assert!(check_appendable(&path).is_ok());
+
+    fs::write(&path, "line").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    assert!(check_appendable(&path).is_ok());
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o666)).unwrap();
+    assert!(check_appendable(&path).is_err());
+}
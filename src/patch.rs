@@ -0,0 +1,134 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// Checks that `patch` applies cleanly, then applies it. Nothing is changed
+/// if the check fails. Inside a git repository the patch is applied from its
+/// top level, which is what the paths in a git diff are relative to; the
+/// directory used is returned.
+pub fn apply(patch: &str, dir: &Path) -> Result<PathBuf, String> {
+    let patch = with_trailing_newline(patch);
+    let dir = repository_root(dir).unwrap_or_else(|| dir.to_path_buf());
+    run_tool(&patch, &dir, true)?;
+    run_tool(&patch, &dir, false)?;
+    Ok(dir)
+}
+
+fn repository_root(dir: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8(output.stdout).ok()?;
+    Some(PathBuf::from(root.trim_end_matches(['\r', '\n'])))
+}
+
+/// `git apply` and `patch` both reject a final hunk line without a newline,
+/// which is how most clipboards hand it over.
+pub fn with_trailing_newline(patch: &str) -> String {
+    let mut patch = patch.to_string();
+    if !patch.ends_with('\n') {
+        patch.push('\n');
+    }
+    patch
+}
+
+// `git apply` works outside repositories too; fall back to `patch` when git
+// isn't installed
+fn run_tool(patch: &str, dir: &Path, dry_run: bool) -> Result<(), String> {
+    let mut git = Command::new("git");
+    git.args(["apply", "--verbose"]);
+    if dry_run {
+        git.arg("--check");
+    }
+    match run(git, patch, dir) {
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        result => {
+            let output = finish(result, dry_run, None)?;
+            // git apply skips files outside the directory it runs in and
+            // still succeeds; --verbose lists the ones it did look at
+            if !checked_any(&String::from_utf8_lossy(&output.stderr)) {
+                return Err(format!(
+                    "Patch does not apply:\nnone of its files are under '{}'",
+                    dir.display()
+                ));
+            }
+            return Ok(());
+        }
+    }
+
+    let mut fallback = Command::new("patch");
+    fallback.args(["-p1", "--forward", "--batch"]);
+    if dry_run {
+        fallback.arg("--dry-run");
+    }
+    match run(fallback, patch, dir) {
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            Err("--apply needs either git or patch installed".to_string())
+        }
+        result => finish(result, dry_run, Some(PATCH_FALLBACK)).map(drop),
+    }
+}
+
+/// Whether `git apply --verbose` looked at any file, rather than skipping
+/// them all.
+fn checked_any(stderr: &str) -> bool {
+    stderr
+        .lines()
+        .any(|line| line.starts_with("Checking patch "))
+}
+
+const PATCH_FALLBACK: &str = "(git isn't installed, so this used `patch -p1`, which doesn't \
+    follow git's renames, mode changes or binary diffs)";
+
+fn run(mut command: Command, patch: &str, dir: &Path) -> std::io::Result<Output> {
+    let mut child = command
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(patch.as_bytes())?;
+    child.wait_with_output()
+}
+
+fn finish(
+    result: std::io::Result<Output>,
+    dry_run: bool,
+    note: Option<&str>,
+) -> Result<Output, String> {
+    let output = result.map_err(|e| format!("Error running patch tool: {e}"))?;
+    if output.status.success() {
+        return Ok(output);
+    }
+
+    let mut details = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if details.is_empty() {
+        details = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    }
+    if let Some(note) = note {
+        details.push_str(&format!("\n{note}"));
+    }
+    let what = if dry_run {
+        "Patch does not apply"
+    } else {
+        "Error applying patch"
+    };
+    Err(format!("{what}:\n{details}"))
+}
+
+#[cfg(test)]
+#[path = "patch_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::patch::*;
+use std::fs;
+use std::process::Command;
+
+const PATCH: &str = "diff --git a/greeting.txt b/greeting.txt
+--- a/greeting.txt
++++ b/greeting.txt
+@@ -1,2 +1,2 @@
+ hello
+-world
++there";
+
+#[test]
+fn test_apply_patch() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("greeting.txt");
+    fs::write(&path, "hello\nworld\n").unwrap();
+
+    apply(PATCH, dir.path()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello\nthere\n");
+}
+
+#[test]
+fn test_failed_check_leaves_files_alone() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("greeting.txt");
+    fs::write(&path, "hello\nsomething else\n").unwrap();
+
+    let error = apply(PATCH, dir.path()).unwrap_err();
+    assert!(error.starts_with("Patch does not apply"), "{error}");
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "hello\nsomething else\n"
+    );
+}
+
+#[test]
+fn test_apply_from_repository_root() {
+    // paths in a git diff are relative to the top of the repository, not
+    // the directory c2f runs in
+    let dir = tempfile::tempdir().unwrap();
+    let status = Command::new("git")
+        .args(["init", "--quiet"])
+        .current_dir(dir.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let path = dir.path().join("greeting.txt");
+    fs::write(&path, "hello\nworld\n").unwrap();
+    let subdir = dir.path().join("src");
+    fs::create_dir(&subdir).unwrap();
+
+    let root = apply(PATCH, &subdir).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello\nthere\n");
+    assert_eq!(
+        root.canonicalize().unwrap(),
+        dir.path().canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn test_checked_any() {
+    assert!(checked_any("Checking patch greeting.txt...\n"));
+    // what git apply prints, successfully, for files outside its directory
+    assert!(!checked_any("Skipped patch 'greeting.txt'.\n"));
+}
+
+#[test]
+fn test_with_trailing_newline() {
+    assert_eq!(with_trailing_newline("+a"), "+a\n");
+    assert_eq!(with_trailing_newline("+a\n"), "+a\n");
+}
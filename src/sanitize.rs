@@ -0,0 +1,79 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Typography that sneaks into code copied from docs, chat and PDFs: curly
+// quotes, dashes, non-breaking and zero-width spaces, and ligatures. None of
+// it survives a compiler or a config parser, and most of it is invisible.
+// Bidirectional controls are only reported: they have real uses in
+// right-to-left strings, but can also make code read differently than it
+// runs ("Trojan Source").
+
+/// Replaces typographic characters with their plain ASCII forms. Returns
+/// the new text and how many characters of each kind were replaced, in the
+/// order they were first seen.
+pub fn sanitize(text: &str) -> (String, Vec<(&'static str, usize)>) {
+    let mut out = String::with_capacity(text.len());
+    let mut changes: Vec<(&'static str, usize)> = Vec::new();
+    let mut previous = None;
+    for ch in text.chars() {
+        let replacement = match ch {
+            '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' => Some(("smart quotes", "'")),
+            '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' => Some(("smart quotes", "\"")),
+            '\u{2013}' | '\u{2212}' => Some(("dashes", "-")),
+            // what chat apps make of `--`
+            '\u{2014}' => Some(("dashes", "--")),
+            '\u{2026}' => Some(("ellipses", "...")),
+            '\u{a0}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' => {
+                Some(("non-breaking and odd-width spaces", " "))
+            }
+            // joins emoji sequences like family emoji, so it stays there
+            '\u{200d}' if previous.map_or(false, is_emoji) => None,
+            '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{2060}' | '\u{ad}' => {
+                Some(("zero-width characters", ""))
+            }
+            // a byte order mark anywhere but the start
+            '\u{feff}' if previous.is_some() => Some(("zero-width characters", "")),
+            '\u{fb00}' => Some(("ligatures", "ff")),
+            '\u{fb01}' => Some(("ligatures", "fi")),
+            '\u{fb02}' => Some(("ligatures", "fl")),
+            '\u{fb03}' => Some(("ligatures", "ffi")),
+            '\u{fb04}' => Some(("ligatures", "ffl")),
+            '\u{fb05}' | '\u{fb06}' => Some(("ligatures", "st")),
+            _ => None,
+        };
+        match replacement {
+            Some((kind, plain)) => {
+                out.push_str(plain);
+                match changes.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, count)) => *count += 1,
+                    None => changes.push((kind, 1)),
+                }
+            }
+            None => out.push(ch),
+        }
+        previous = Some(ch);
+    }
+    (out, changes)
+}
+
+fn is_emoji(ch: char) -> bool {
+    matches!(ch, '\u{2600}'..='\u{27bf}' | '\u{1f000}'..='\u{1faff}' | '\u{fe0f}')
+}
+
+/// Lines, 1-based, holding bidirectional control characters.
+pub fn bidi_lines(text: &str) -> Vec<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.contains([
+                '\u{202a}', '\u{202b}', '\u{202c}', '\u{202d}', '\u{202e}', '\u{2066}', '\u{2067}',
+                '\u{2068}', '\u{2069}', '\u{200e}', '\u{200f}', '\u{61c}',
+            ])
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "sanitize_tests.rs"]
+mod tests;
@@ -0,0 +1,53 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::sanitize::*;
+
+#[test]
+fn test_sanitize() {
+    let text = "print(\u{201c}hello\u{201d})\nname\u{a0}=\u{a0}\u{2018}x\u{2019}\n\
+        curl \u{2014}silent \u{2013}L\nde\u{fb01}ne\u{200b}d\n";
+    let (clean, changes) = sanitize(text);
+    assert_eq!(
+        clean,
+        "print(\"hello\")\nname = 'x'\ncurl --silent -L\ndefined\n"
+    );
+    assert_eq!(
+        changes,
+        vec![
+            ("smart quotes", 4),
+            ("non-breaking and odd-width spaces", 2),
+            ("dashes", 2),
+            ("ligatures", 1),
+            ("zero-width characters", 1),
+        ]
+    );
+}
+
+#[test]
+fn test_sanitize_leaves_plain_text() {
+    let text = "let s = \"caf\u{e9} \u{4e2d}\u{6587}\"; // \u{2192} ok\n";
+    assert_eq!(sanitize(text), (text.to_string(), Vec::new()));
+}
+
+#[test]
+fn test_sanitize_keeps_emoji_joiners() {
+    // family emoji stay joined, a stray joiner goes
+    let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+    let (clean, changes) = sanitize(&format!("s = \"{family}\"\na\u{200d}b\n"));
+    assert_eq!(clean, format!("s = \"{family}\"\nab\n"));
+    assert_eq!(changes, vec![("zero-width characters", 1)]);
+}
+
+#[test]
+fn test_sanitize_byte_order_mark() {
+    let (clean, _) = sanitize("\u{feff}a\u{feff}b");
+    assert_eq!(clean, "\u{feff}ab");
+}
+
+#[test]
+fn test_bidi_lines() {
+    let text = "ok\nif (admin \u{202e}) {\n}\n// \u{2066}x\u{2069}\n";
+    assert_eq!(bidi_lines(text), vec![2, 4]);
+    assert!(bidi_lines("plain\n").is_empty());
+}
@@ -0,0 +1,348 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Bounded, single-pass text scanning for the detectors. Detection looks at
+// a window of at most `max_bytes` and finds every keyword the detectors ask
+// about in one Aho-Corasick pass, so a huge paste costs the same as a
+// medium one and no detector walks the text once per keyword.
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+// Every substring a detector checks with `Scan::contains`, by detector
+// module. Also matched ASCII case-insensitively for `contains_ignore_case`.
+const DATA: &[&str] = &[
+    "<div",
+    "<span",
+    "<p>",
+    "<body",
+    "<html",
+    "<!doctype",
+    "<h1",
+    "<h2",
+    "<a ",
+    "<img",
+    "<table",
+    "<ul>",
+    "<?php",
+    "</",
+    ":\n",
+    "[",
+    "]",
+    " = ",
+    "SELECT ",
+    "INSERT ",
+    "UPDATE ",
+    "DELETE ",
+    "CREATE TABLE",
+    "ALTER TABLE",
+    "\"nbformat\"",
+    "\"cells\"",
+    "message ",
+    " = 1;",
+    "service ",
+    "rpc ",
+];
+const LANG: &[&str] = &[
+    "fn ",
+    "impl ",
+    "use ",
+    "struct ",
+    "enum ",
+    "trait ",
+    "let mut ",
+    "match ",
+    "def ",
+    "class ",
+    "if __name__",
+    "print(",
+    "interface ",
+    "type ",
+    ": string",
+    ": number",
+    ": boolean",
+    ": void",
+    "const ",
+    "let ",
+    "var ",
+    "function ",
+    "=>",
+    "console.log(",
+    "module.exports",
+    "package ",
+    "func ",
+    "import (",
+    " struct",
+    "public class ",
+    "private ",
+    "public static void main",
+    "import java.",
+    "System.out.print",
+    "using System",
+    "namespace ",
+    "public static class ",
+    "static void Main",
+    "#include <",
+    "std::",
+    "cout <<",
+    "::",
+    "#include <stdio.h>",
+    "#include <stdlib.h>",
+    "int main(",
+    "void ",
+    "const char *",
+    "size_t ",
+    "echo ",
+    "if [",
+    "$PSVersionTable",
+    "Get-",
+    "Set-",
+    "Write-Host",
+    "puts ",
+    "require ",
+    "end",
+    "<?=",
+    "$",
+    "import Foundation",
+    "import UIKit",
+    " = .",
+    ": ",
+    "fun ",
+    "val ",
+    "import kotlin.",
+    "/>",
+    "return (",
+    "=> (",
+    "return <",
+    "from 'react'",
+    "from \"react\"",
+    "React.",
+    "className=",
+    "useState(",
+    "useEffect(",
+    "={",
+    "type Props",
+    ": React.",
+    "useState<",
+    "import <nixpkgs>",
+    "mkDerivation",
+    "with pkgs;",
+    "}:",
+    "local function ",
+    " then",
+    "elseif ",
+    "require(\"",
+    "local ",
+    "use strict;",
+    "use warnings;",
+    "my $",
+    "my @",
+    "my %",
+    "library(",
+    "<- function(",
+    " <- c(",
+    "<- data.frame(",
+    "import scala.",
+    "case class ",
+    "sealed trait ",
+    "extends App",
+    "implicit ",
+    "def main(args: Array[String])",
+    "object ",
+    " where",
+    "import qualified ",
+    "main = do",
+    "defmodule ",
+    "|>",
+    " do",
+    ":ok",
+    "import 'package:",
+    "import 'dart:",
+    "Widget build(",
+    ") async {",
+    "void main()",
+    "final ",
+    "@import(\"",
+    "!void",
+    "comptime ",
+];
+const MARKUP: &[&str] = &[
+    "<!DOCTYPE",
+    "</div>",
+    "</span>",
+    "</body>",
+    "</html>",
+    "\n# ",
+    "\n## ",
+    "```",
+    "](",
+    "\\documentclass",
+    "\\begin{",
+    "\\section{",
+    "\\usepackage{",
+    "{",
+    "}",
+    "color:",
+    "font-",
+    "margin:",
+    "padding:",
+    ":",
+    ";",
+    "@mixin",
+    "@include",
+    "@extend",
+    "<svg",
+    "<template>",
+    "<script",
+    "<style",
+    "{#if ",
+    "{#each ",
+    "on:click",
+    "bind:",
+];
+const SPECIAL: &[&str] = &[
+    "\nFROM ",
+    "RUN ",
+    "CMD ",
+    "EXPOSE ",
+    "WORKDIR ",
+    ":\n\t",
+    ".PHONY:",
+    "\ndiff --git ",
+];
+
+struct Keywords {
+    all: Vec<&'static str>,
+    matcher: AhoCorasick,
+    ids: HashMap<&'static str, usize>,
+}
+
+impl Keywords {
+    fn new() -> Keywords {
+        let all = [DATA, LANG, MARKUP, SPECIAL].concat();
+        let matcher = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .build(&all)
+            .expect("keywords are valid patterns");
+        let ids = all.iter().enumerate().map(|(id, k)| (*k, id)).collect();
+        Keywords { all, matcher, ids }
+    }
+
+    fn id(&self, keyword: &str) -> Option<usize> {
+        self.ids.get(keyword).copied()
+    }
+}
+
+thread_local! {
+    // built on first use, once per thread
+    static MATCHER: Keywords = Keywords::new();
+}
+
+/// Text prepared for the detectors: derefs to the text itself, and answers
+/// `contains` for the known keywords from a single pass.
+pub struct Scan<'a> {
+    text: &'a str,
+    exact: Vec<bool>,
+    caseless: Vec<bool>,
+}
+
+impl<'a> Scan<'a> {
+    pub fn new(text: &'a str) -> Scan<'a> {
+        MATCHER.with(|keywords| {
+            let mut exact = vec![false; keywords.all.len()];
+            let mut caseless = vec![false; keywords.all.len()];
+            for m in keywords.matcher.find_overlapping_iter(text) {
+                let id = m.pattern().as_usize();
+                caseless[id] = true;
+                if &text[m.range()] == keywords.all[id] {
+                    exact[id] = true;
+                }
+            }
+            Scan {
+                text,
+                exact,
+                caseless,
+            }
+        })
+    }
+
+    /// Whether `keyword` occurs. Keywords in the lists above are answered
+    /// from the scan; others are searched for in the text.
+    pub fn contains(&self, keyword: &str) -> bool {
+        match MATCHER.with(|k| k.id(keyword)) {
+            Some(id) => self.exact[id],
+            None => self.text.contains(keyword),
+        }
+    }
+
+    /// Like `contains`, ignoring ASCII case.
+    pub fn contains_ignore_case(&self, keyword: &str) -> bool {
+        match MATCHER.with(|k| k.id(keyword)) {
+            Some(id) => self.caseless[id],
+            None => {
+                keyword.is_empty()
+                    || self
+                        .text
+                        .as_bytes()
+                        .windows(keyword.len())
+                        .any(|w| w.eq_ignore_ascii_case(keyword.as_bytes()))
+            }
+        }
+    }
+}
+
+impl Deref for Scan<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+/// The part of `text` detection looks at: all of it up to `max_bytes`
+/// (0 means no limit), otherwise the start and the end joined by a line
+/// break. The start gets most of the budget since that's where shebangs,
+/// headers and first rows are; the end keeps closing brackets paired up.
+/// Cuts fall on line breaks where possible so no partial lines are seen.
+pub fn window(text: &str, max_bytes: usize) -> Cow<'_, str> {
+    match window_bounds(text.as_bytes(), max_bytes) {
+        None => Cow::Borrowed(text),
+        Some((head, tail)) => Cow::Owned(format!("{}\n{}", &text[..head], &text[tail..])),
+    }
+}
+
+/// `window` for raw bytes, so huge files aren't decoded in full.
+pub fn window_bytes(bytes: &[u8], max_bytes: usize) -> Cow<'_, [u8]> {
+    match window_bounds(bytes, max_bytes) {
+        None => Cow::Borrowed(bytes),
+        Some((head, tail)) => Cow::Owned([&bytes[..head], b"\n", &bytes[tail..]].concat()),
+    }
+}
+
+fn window_bounds(bytes: &[u8], max_bytes: usize) -> Option<(usize, usize)> {
+    if max_bytes == 0 || bytes.len() <= max_bytes {
+        return None;
+    }
+    let mut head = max_bytes * 3 / 4;
+    let mut tail = bytes.len() - max_bytes / 4;
+    if let Some(i) = bytes[..head].iter().rposition(|&b| b == b'\n') {
+        head = i;
+    }
+    if let Some(i) = bytes[tail..].iter().position(|&b| b == b'\n') {
+        tail += i + 1;
+    }
+    // a single huge line: stay on UTF-8 character boundaries
+    while head > 0 && bytes[head] & 0xC0 == 0x80 {
+        head -= 1;
+    }
+    while tail < bytes.len() && bytes[tail] & 0xC0 == 0x80 {
+        tail += 1;
+    }
+    Some((head, tail))
+}
+
+#[cfg(test)]
+#[path = "scan_tests.rs"]
+mod tests;
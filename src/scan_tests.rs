@@ -0,0 +1,71 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::scan::*;
+
+#[test]
+fn test_scan_contains() {
+    let scan = Scan::new("SELECT * FROM users;\nfn main() {}");
+    assert!(scan.contains("SELECT "));
+    assert!(scan.contains("fn "));
+    assert!(scan.contains(";"));
+    assert!(!scan.contains("struct "));
+    // overlapping keywords are all found
+    assert!(scan.contains("{") && scan.contains("}"));
+    // the text itself is still there
+    assert!(scan.starts_with("SELECT"));
+}
+
+#[test]
+fn test_scan_ignore_case() {
+    let scan = Scan::new("select id from users");
+    assert!(!scan.contains("SELECT "));
+    assert!(scan.contains_ignore_case("SELECT "));
+
+    let scan = Scan::new("<!DOCTYPE html>");
+    assert!(scan.contains("<!DOCTYPE"));
+    assert!(!scan.contains("<!doctype"));
+    assert!(scan.contains_ignore_case("<!doctype"));
+}
+
+#[test]
+fn test_scan_unknown_keyword() {
+    // keywords missing from the lists fall back to searching the text
+    let scan = Scan::new("Not Registered here");
+    assert!(scan.contains("Registered"));
+    assert!(!scan.contains("not registered"));
+    assert!(scan.contains_ignore_case("not registered"));
+    assert!(!scan.contains_ignore_case("elsewhere"));
+    assert!(scan.contains_ignore_case(""));
+}
+
+#[test]
+fn test_window_small_input() {
+    assert_eq!(window("short", 100), "short");
+    let text = "x\n".repeat(1000);
+    assert_eq!(window(&text, 0), text);
+}
+
+#[test]
+fn test_window_keeps_start_and_end() {
+    let text: String = (0..10_000).map(|i| format!("line {i}\n")).collect();
+    let sample = window(&text, 1000);
+    assert!(sample.len() <= 1000);
+    assert!(sample.starts_with("line 0\nline 1\n"));
+    assert!(sample.ends_with("line 9999\n"));
+    // only whole lines
+    assert!(sample.lines().all(|l| l.starts_with("line ")));
+    assert!(!sample.contains("line 5000\n"));
+}
+
+#[test]
+fn test_window_char_boundaries() {
+    // one long line of multi-byte characters: cuts must not split them
+    let text = "é".repeat(10_000);
+    let sample = window(&text, 1001);
+    assert!(sample.len() <= 1001);
+    assert!(sample.starts_with('é') && sample.ends_with('é'));
+
+    let bytes = window_bytes(text.as_bytes(), 1001);
+    assert!(std::str::from_utf8(&bytes).is_ok());
+}
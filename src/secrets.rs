@@ -0,0 +1,311 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecretPolicy {
+    Warn,
+    Refuse,
+    Redact,
+    Ignore,
+}
+
+impl SecretPolicy {
+    pub const VALUES: &'static [&'static str] = &["warn", "refuse", "redact", "ignore"];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "warn" => Some(SecretPolicy::Warn),
+            "refuse" => Some(SecretPolicy::Refuse),
+            "redact" => Some(SecretPolicy::Redact),
+            "ignore" => Some(SecretPolicy::Ignore),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub kind: &'static str,
+    // 1-based, filled in by `scan` once findings are sorted
+    pub line: usize,
+    // byte range of the secret within the scanned text
+    pub start: usize,
+    pub end: usize,
+}
+
+const CREDENTIAL_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "access_key",
+    "private_key",
+    "secret_key",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+pub fn scan(text: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    scan_private_keys(text, &mut findings);
+    scan_assignments(text, &mut findings);
+    scan_tokens(text, &mut findings);
+
+    // keep the earliest, widest match when detectors overlap
+    findings.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut result: Vec<Finding> = Vec::new();
+    for finding in findings {
+        if result.last().map_or(true, |last| finding.start >= last.end) {
+            result.push(finding);
+        }
+    }
+
+    // line numbers in one pass over the text, now the findings are in order
+    let (mut line, mut counted) = (1, 0);
+    for finding in &mut result {
+        line += text[counted..finding.start].matches('\n').count();
+        counted = finding.start;
+        finding.line = line;
+    }
+    result
+}
+
+pub fn redact(text: &str, findings: &[Finding]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    for finding in findings {
+        out.push_str(&text[pos..finding.start]);
+        out.push_str(REDACTED);
+        pos = finding.end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+fn scan_private_keys(text: &str, findings: &mut Vec<Finding>) {
+    let mut search = 0;
+    while let Some(found) = text[search..].find("-----BEGIN ") {
+        let start = search + found;
+        let header_end = match text[start..].find('\n') {
+            Some(i) => start + i,
+            None => text.len(),
+        };
+        if !text[start..header_end].contains("PRIVATE KEY-----") {
+            search = header_end;
+            continue;
+        }
+
+        // the block runs until the matching footer, or to the end if truncated
+        let end = match text[header_end..].find("-----END ") {
+            Some(i) => {
+                let footer = header_end + i;
+                match text[footer + 9..].find("-----") {
+                    Some(j) => footer + 9 + j + 5,
+                    None => text.len(),
+                }
+            }
+            None => text.len(),
+        };
+
+        findings.push(Finding {
+            kind: "Private key",
+            line: 0,
+            start,
+            end,
+        });
+        search = end;
+    }
+}
+
+fn scan_assignments(text: &str, findings: &mut Vec<Finding>) {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+        let separator = match line.find(['=', ':']) {
+            Some(i) => i,
+            None => continue,
+        };
+
+        let key = line[..separator]
+            .trim()
+            .trim_start_matches("export ")
+            .trim_matches(|c| c == '"' || c == '\'');
+        if key.is_empty() || key.contains(' ') || !is_credential_key(key) {
+            continue;
+        }
+
+        let raw = &line[separator + 1..];
+        let value = raw
+            .trim()
+            .trim_end_matches(|c: char| c == ',' || c == ';' || c == '}' || c.is_whitespace());
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+        if value.len() < 4 || is_placeholder(value) {
+            continue;
+        }
+
+        let start = line_start + separator + 1 + raw.find(value).unwrap_or(0);
+        findings.push(Finding {
+            kind: "Credential assignment",
+            line: 0,
+            start,
+            end: start + value.len(),
+        });
+    }
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    lower.starts_with("${")
+        || lower.starts_with('<')
+        || lower.chars().all(|c| c == '*' || c == 'x' || c == '.')
+        || ["changeme", "example", "placeholder", "your_", "redacted"]
+            .iter()
+            .any(|p| lower.contains(p))
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+' | '/' | '.')
+}
+
+fn scan_tokens(text: &str, findings: &mut Vec<Finding>) {
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (start, is_token_char(c)) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                let token = text[s..i].trim_end_matches('.');
+                if let Some(kind) = classify_token(token) {
+                    findings.push(Finding {
+                        kind,
+                        line: 0,
+                        start: s,
+                        end: s + token.len(),
+                    });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn classify_token(token: &str) -> Option<&'static str> {
+    if is_aws_key(token) {
+        return Some("AWS access key ID");
+    }
+    if is_github_token(token) {
+        return Some("GitHub token");
+    }
+    if token.starts_with("xox") && token.len() > 15 && token.as_bytes().get(4) == Some(&b'-') {
+        return Some("Slack token");
+    }
+    if token.starts_with("sk_live_") || token.starts_with("rk_live_") {
+        return Some("Stripe secret key");
+    }
+    if is_jwt(token) {
+        return Some("JSON Web Token");
+    }
+    if is_high_entropy(token) {
+        return Some("High-entropy string");
+    }
+    None
+}
+
+fn is_aws_key(token: &str) -> bool {
+    token.len() == 20
+        && (token.starts_with("AKIA") || token.starts_with("ASIA"))
+        && token[4..]
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+fn is_github_token(token: &str) -> bool {
+    let body_ok = |body: &str, min: usize| {
+        body.len() >= min && body.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    ["ghp_", "gho_", "ghu_", "ghs_", "ghr_"]
+        .iter()
+        .any(|p| token.strip_prefix(p).map_or(false, |b| body_ok(b, 36)))
+        || token
+            .strip_prefix("github_pat_")
+            .map_or(false, |b| body_ok(b, 22))
+}
+
+fn is_jwt(token: &str) -> bool {
+    let parts: Vec<&str> = token.split('.').collect();
+    parts.len() == 3
+        && parts[0].starts_with("eyJ")
+        && parts[1].starts_with("eyJ")
+        && parts[2].len() >= 16
+        && parts.iter().all(|p| {
+            p.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// Whether `key` ends in one of `CREDENTIAL_KEYS`, comparing whole words so
+/// `DB_PASSWORD` and `apiKey` match but `max_tokens` doesn't.
+fn is_credential_key(key: &str) -> bool {
+    let mut words: Vec<String> = Vec::new();
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if !c.is_alphanumeric() {
+            words.push(String::new());
+        } else if words.is_empty() || prev_lower && c.is_uppercase() {
+            words.push(c.to_lowercase().collect());
+        } else if let Some(word) = words.last_mut() {
+            word.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase();
+    }
+    CREDENTIAL_KEYS.iter().any(|k| {
+        let parts: Vec<&str> = k.split('_').collect();
+        words.len() >= parts.len() && words[words.len() - parts.len()..] == parts[..]
+    })
+}
+
+fn is_high_entropy(token: &str) -> bool {
+    if token.len() < 24 || token.contains('.') || token.contains('/') && token.len() < 40 {
+        return false;
+    }
+    // commit hashes and checksums are hex, and they're everywhere
+    if token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    let has_digit = token.chars().any(|c| c.is_ascii_digit());
+    let has_upper = token.chars().any(|c| c.is_ascii_uppercase());
+    let has_lower = token.chars().any(|c| c.is_ascii_lowercase());
+    has_digit && has_upper && has_lower && shannon_entropy(token) > 4.0
+}
+
+fn shannon_entropy(token: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in token.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    let len = token.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+#[path = "secrets_tests.rs"]
+mod tests;
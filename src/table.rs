@@ -0,0 +1,347 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Table conversion between CSV, TSV, Markdown tables and JSON, driven by the
+// extension of the file being saved. Every format is read into CSV records
+// (rows of fields, the first row being the header) and written from them,
+// so CSV is the hub every conversion goes through.
+
+use crate::detect::ContentType;
+use serde_json::{Map, Value};
+
+type Records = Vec<Vec<String>>;
+
+/// Converts a table in `text` from one format to another. `None` when the
+/// types aren't two different table formats, or `text` isn't a table.
+pub fn convert(text: &str, from: &ContentType, to: &ContentType) -> Option<String> {
+    if from == to || !is_table_format(to) {
+        return None;
+    }
+    let records = match from {
+        ContentType::Csv => read_delimited(text, ','),
+        ContentType::Tsv => read_delimited(text, '\t'),
+        ContentType::Markdown => read_markdown(text)?,
+        ContentType::Json => read_json(text)?,
+        _ => return None,
+    };
+    if records.is_empty() {
+        return None;
+    }
+    Some(match to {
+        ContentType::Csv => write_delimited(&records, ','),
+        ContentType::Tsv => write_delimited(&records, '\t'),
+        ContentType::Markdown => write_markdown(&records),
+        _ => write_json(&records),
+    })
+}
+
+/// CSV, TSV, Markdown or JSON, which `convert` goes between.
+pub fn is_table_format(content_type: &ContentType) -> bool {
+    matches!(
+        content_type,
+        ContentType::Csv | ContentType::Tsv | ContentType::Markdown | ContentType::Json
+    )
+}
+
+/// Reads CSV-style records: fields in double quotes may hold the delimiter,
+/// line breaks and doubled quotes. Spreadsheets quote TSV cells the same
+/// way. Blank lines are skipped.
+fn read_delimited(text: &str, delimiter: char) -> Records {
+    let (records, _) = read_numbered(text, delimiter);
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+/// Checks that every record of CSV or TSV text has as many fields as the
+/// header, and that no quoted field is left open.
+pub fn check_columns(text: &str, delimiter: char) -> Result<(), String> {
+    let (records, open_quote) = read_numbered(text, delimiter);
+    if let Some(line) = open_quote {
+        return Err(format!("line {line}: quoted field is never closed"));
+    }
+    let Some((_, header)) = records.first() else {
+        return Ok(());
+    };
+    for (line, record) in &records[1..] {
+        if record.len() != header.len() {
+            return Err(format!(
+                "line {line}: expected {} fields like the header, found {}",
+                header.len(),
+                record.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Records with the line each starts on, and the line of a quoted field
+/// still open at the end of the text.
+fn read_numbered(text: &str, delimiter: char) -> (Vec<(usize, Vec<String>)>, Option<usize>) {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = None;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\n' || (ch == '\r' && chars.peek() != Some(&'\n')) {
+            line += 1;
+        }
+        if quoted.is_some() {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = None,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' if field.is_empty() => quoted = Some(line),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                end_record(&mut records, &mut record, &mut field, start);
+                start = line;
+            }
+            _ if ch == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(ch),
+        }
+    }
+    end_record(&mut records, &mut record, &mut field, start);
+    (records, quoted)
+}
+
+fn end_record(
+    records: &mut Vec<(usize, Vec<String>)>,
+    record: &mut Vec<String>,
+    field: &mut String,
+    line: usize,
+) {
+    record.push(std::mem::take(field));
+    let record = std::mem::take(record);
+    if record.len() > 1 || !record[0].is_empty() {
+        records.push((line, record));
+    }
+}
+
+fn write_delimited(records: &Records, delimiter: char) -> String {
+    let mut out = String::new();
+    for record in records {
+        let fields: Vec<String> = record
+            .iter()
+            .map(|field| {
+                if field.contains([delimiter, '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        out.push_str(&fields.join(&delimiter.to_string()));
+        out.push('\n');
+    }
+    out
+}
+
+/// Reads text that is a Markdown table and nothing else: a header row, a
+/// `| --- |` separator row and the body rows.
+fn read_markdown(text: &str) -> Option<Records> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    if lines.len() < 2 || !lines.iter().all(|l| l.contains('|')) {
+        return None;
+    }
+    let is_separator = |cell: &String| {
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+    };
+    if !markdown_cells(lines[1]).iter().all(is_separator) {
+        return None;
+    }
+    let mut records = vec![markdown_cells(lines[0])];
+    records.extend(lines[2..].iter().map(|l| markdown_cells(l)));
+    Some(records)
+}
+
+fn markdown_cells(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(inner) if !inner.ends_with('\\') => inner,
+        _ => line,
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(ch),
+        }
+    }
+    cells.push(cell);
+    cells
+        .iter()
+        .map(|c| c.trim().replace("<br>", "\n"))
+        .collect()
+}
+
+/// Writes a Markdown table with its columns padded to line up.
+fn write_markdown(records: &Records) -> String {
+    let columns = records.iter().map(Vec::len).max().unwrap_or(0);
+    let cells: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| {
+            (0..columns)
+                .map(|c| {
+                    let field = record.get(c).map_or("", String::as_str);
+                    field
+                        .replace('|', "\\|")
+                        .replace("\r\n", "<br>")
+                        .replace('\n', "<br>")
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| {
+            cells
+                .iter()
+                .map(|row| row[c].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+
+    let line = |row: &[String]| {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.chars().count())))
+            .collect();
+        format!("| {} |\n", padded.join(" | "))
+    };
+    let mut out = line(&cells[0]);
+    let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&line(&separator));
+    for row in &cells[1..] {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+/// Reads a JSON array of objects (columns are the keys, in order of first
+/// appearance) or of arrays (the first one being the header).
+fn read_json(text: &str) -> Option<Records> {
+    let rows = match serde_json::from_str(text).ok()? {
+        Value::Array(rows) if !rows.is_empty() => rows,
+        _ => return None,
+    };
+    if rows.iter().all(Value::is_array) {
+        let records = rows
+            .iter()
+            .filter_map(Value::as_array)
+            .map(|row| row.iter().map(field).collect())
+            .collect();
+        return Some(records);
+    }
+
+    let objects: Vec<&Map<String, Value>> =
+        rows.iter().map(Value::as_object).collect::<Option<_>>()?;
+    let mut header: Vec<String> = Vec::new();
+    for key in objects.iter().flat_map(|object| object.keys()) {
+        if !header.contains(key) {
+            header.push(key.clone());
+        }
+    }
+    let mut records = vec![header.clone()];
+    for object in objects {
+        let record = header
+            .iter()
+            .map(|key| object.get(key).map(field).unwrap_or_default())
+            .collect();
+        records.push(record);
+    }
+    Some(records)
+}
+
+fn field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        // numbers, booleans, and nested values as compact JSON
+        other => other.to_string(),
+    }
+}
+
+/// Writes an array of objects keyed by the header. Fields that are JSON
+/// numbers or booleans as written become those, empty fields null, and
+/// everything else stays a string, so "007" or "1e3" are kept as text.
+fn write_json(records: &Records) -> String {
+    let columns = records.iter().map(Vec::len).max().unwrap_or(0);
+    let mut keys: Vec<String> = Vec::new();
+    for c in 0..columns {
+        let name = records[0].get(c).map_or("", |k| k.trim());
+        let base = if name.is_empty() {
+            format!("column{}", c + 1)
+        } else {
+            name.to_string()
+        };
+        let mut key = base.clone();
+        let mut n = 2;
+        while keys.contains(&key) {
+            key = format!("{base}_{n}");
+            n += 1;
+        }
+        keys.push(key);
+    }
+
+    let rows: Vec<Value> = records[1..]
+        .iter()
+        .map(|record| {
+            let object = keys
+                .iter()
+                .enumerate()
+                .map(|(c, key)| {
+                    (
+                        key.clone(),
+                        json_value(record.get(c).map_or("", String::as_str)),
+                    )
+                })
+                .collect();
+            Value::Object(object)
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&Value::Array(rows)).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+fn json_value(field: &str) -> Value {
+    if field.is_empty() {
+        return Value::Null;
+    }
+    match serde_json::from_str::<Value>(field) {
+        // only when it reads back the same, so "1.50" or "-0" stay strings
+        Ok(value @ (Value::Number(_) | Value::Bool(_)))
+            if serde_json::to_string(&value).ok().as_deref() == Some(field) =>
+        {
+            value
+        }
+        _ => Value::String(field.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[path = "table_tests.rs"]
+mod tests;
@@ -0,0 +1,118 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType::{self, Csv, Json, Markdown, Tsv};
+use crate::table::*;
+
+fn conv(text: &str, from: ContentType, to: ContentType) -> String {
+    convert(text, &from, &to).unwrap()
+}
+
+#[test]
+fn test_tsv_to_csv() {
+    // spreadsheet copies: quoted cells may hold tabs and line breaks
+    let tsv = "name\tnote\r\nAda\t\"first, \"\"the\"\" one\"\r\nAlan\t\"two\nlines\"\r\n";
+    assert_eq!(
+        conv(tsv, Tsv, Csv),
+        "name,note\nAda,\"first, \"\"the\"\" one\"\nAlan,\"two\nlines\"\n"
+    );
+    assert_eq!(conv("a,b\n1,\"x\ty\"\n", Csv, Tsv), "a\tb\n1\t\"x\ty\"\n");
+}
+
+#[test]
+fn test_to_markdown() {
+    let csv = "name,role\nAda,engineer\nBob,a|b\n";
+    assert_eq!(
+        conv(csv, Csv, Markdown),
+        "| name | role     |\n| ---- | -------- |\n| Ada  | engineer |\n| Bob  | a\\|b     |\n"
+    );
+    // ragged rows are padded
+    assert_eq!(
+        conv("a,b\n1\n", Csv, Markdown),
+        "| a   | b   |\n| --- | --- |\n| 1   |     |\n"
+    );
+}
+
+#[test]
+fn test_from_markdown() {
+    let markdown = "| Name | Count |\n|:-----|------:|\n| a\\|b | 1 |\n| c<br>d | 2 |\n";
+    assert_eq!(
+        conv(markdown, Markdown, Csv),
+        "Name,Count\na|b,1\n\"c\nd\",2\n"
+    );
+    // Markdown that isn't just a table is left alone
+    assert_eq!(
+        convert("# Title\n\n| a |\n|---|\n| 1 |", &Markdown, &Csv),
+        None
+    );
+    assert_eq!(convert("a | b\nno separator", &Markdown, &Csv), None);
+}
+
+#[test]
+fn test_to_json() {
+    let csv = "id,name,score,active,zip,note\n1,Ada,9.5,true,01234,\n2,Bob,-3,false,99999,x\n";
+    assert_eq!(
+        conv(csv, Csv, Json),
+        r#"[
+  {
+    "id": 1,
+    "name": "Ada",
+    "score": 9.5,
+    "active": true,
+    "zip": "01234",
+    "note": null
+  },
+  {
+    "id": 2,
+    "name": "Bob",
+    "score": -3,
+    "active": false,
+    "zip": 99999,
+    "note": "x"
+  }
+]
+"#
+    );
+    // empty and repeated headers get usable keys
+    assert_eq!(
+        conv("a,,a\n1,2,3\n", Csv, Json),
+        "[\n  {\n    \"a\": 1,\n    \"column2\": 2,\n    \"a_2\": 3\n  }\n]\n"
+    );
+}
+
+#[test]
+fn test_from_json() {
+    let json = r#"[{"b": 1, "a": "x"}, {"a": "y", "c": [1, 2], "b": null}]"#;
+    assert_eq!(conv(json, Json, Csv), "b,a,c\n1,x,\n,y,\"[1,2]\"\n");
+    assert_eq!(conv("[[\"h\", \"i\"], [1, 2]]", Json, Tsv), "h\ti\n1\t2\n");
+    // JSON that isn't a list of rows isn't a table
+    assert_eq!(convert(r#"{"a": 1}"#, &Json, &Csv), None);
+    assert_eq!(convert("[1, 2]", &Json, &Csv), None);
+    assert_eq!(convert("[]", &Json, &Csv), None);
+}
+
+#[test]
+fn test_not_a_conversion() {
+    assert_eq!(convert("a,b\n1,2", &Csv, &Csv), None);
+    assert_eq!(convert("a,b\n1,2", &Csv, &ContentType::Yaml), None);
+    assert_eq!(convert("a: 1", &ContentType::Yaml, &Csv), None);
+}
+
+#[test]
+fn test_check_columns() {
+    assert_eq!(check_columns("a,b\n1,2\n\n3,4\n", ','), Ok(()));
+    // a quoted line break doesn't end the record
+    assert_eq!(check_columns("a,b\n\"x\ny\",2\n3,4\n", ','), Ok(()));
+    assert_eq!(
+        check_columns("a,b\n\"x\ny\",2\n3\n", ','),
+        Err("line 4: expected 2 fields like the header, found 1".to_string())
+    );
+    assert_eq!(
+        check_columns("a\tb\n1\t2\t3\n", '\t'),
+        Err("line 2: expected 2 fields like the header, found 3".to_string())
+    );
+    assert_eq!(
+        check_columns("a,b\n1,\"open\n2,3\n", ','),
+        Err("line 2: quoted field is never closed".to_string())
+    );
+}
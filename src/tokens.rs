@@ -0,0 +1,93 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Tokenizer for the language classifier. Shared with build.rs, which trains
+// the model with it, so keep it free of crate dependencies.
+
+/// Only this much of the text is tokenized, so classification stays cheap on
+/// huge inputs.
+pub const MAX_BYTES: usize = 16 * 1024;
+
+const NEWLINE: &str = "\u{23ce}";
+// a tab between tokens, i.e. a separator rather than indentation
+const TAB: &str = "\u{21e5}";
+const NUMBER: &str = "0";
+
+// longest first, so `===` wins over `==`
+const OPERATORS: &[&str] = &[
+    "...", "===", "!==", "<=>", "<<-", "::", "->", "=>", "<-", ":=", "|>", "==", "!=", "<=", ">=",
+    "&&", "||", "//", "/*", "*/", "#!", "</", "/>", "{{", "}}", "{%", "%}", "<?", "?>", "--", "++",
+    "..", "<<", ">>", "#[", "@@", "$(", "${", "%>", "<%", "**", "+=", "-=", "?.", "??", "!!",
+];
+
+fn truncate(text: &str) -> &str {
+    if text.len() <= MAX_BYTES {
+        return text;
+    }
+    let mut end = MAX_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn is_word_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Single tokens: words, numbers (collapsed), operators, punctuation and
+/// markers for line starts and separating tabs.
+pub fn unigrams(text: &str) -> Vec<&str> {
+    let text = truncate(text);
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            if tokens.last() != Some(&NEWLINE) {
+                tokens.push(NEWLINE);
+            }
+            rest = &rest[1..];
+        } else if c == '\t' {
+            if !matches!(tokens.last(), None | Some(&NEWLINE) | Some(&TAB)) {
+                tokens.push(TAB);
+            }
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if is_word_start(c) {
+            let end = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+                .unwrap_or(rest.len());
+            tokens.push(NUMBER);
+            rest = &rest[end..];
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            tokens.push(op);
+            rest = &rest[op.len()..];
+        } else {
+            let len = c.len_utf8();
+            // non-ascii text is prose, not syntax
+            if c.is_ascii() {
+                tokens.push(&rest[..len]);
+            }
+            rest = &rest[len..];
+        }
+    }
+    tokens
+}
+
+/// Unigrams plus bigrams of neighbouring tokens, the classifier's features.
+pub fn features(text: &str) -> Vec<String> {
+    let unigrams = unigrams(text);
+    let mut features: Vec<String> = unigrams.iter().map(|t| t.to_string()).collect();
+    features.extend(unigrams.windows(2).map(|w| format!("{} {}", w[0], w[1])));
+    features
+}
@@ -0,0 +1,81 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// Syntax checks for --validate: data formats are parsed before they're
+// saved, so a config copied with a truncated line or smart quotes is caught
+// with the position of the first error. YAML is checked with serde_yaml_ng
+// and XML with roxmltree, for being well-formed only, without schema or DTD
+// validation.
+
+use crate::detect::ContentType;
+use crate::{table, yaml};
+
+/// Parses `text` as `content_type`. `None` when there is no check for the
+/// type, otherwise the first error, starting with its line.
+pub fn check(text: &str, content_type: &ContentType) -> Option<Result<(), String>> {
+    Some(match content_type {
+        ContentType::Json | ContentType::JupyterNotebook => json(text),
+        ContentType::Toml => toml(text),
+        ContentType::Yaml | ContentType::Kubernetes { .. } => yaml::check(text),
+        ContentType::Xml | ContentType::Svg => xml(text),
+        ContentType::Csv => table::check_columns(text, ','),
+        ContentType::Tsv => table::check_columns(text, '\t'),
+        _ => return None,
+    })
+}
+
+fn json(text: &str) -> Result<(), String> {
+    let Err(e) = serde_json::from_str::<serde::de::IgnoredAny>(text) else {
+        return Ok(());
+    };
+    // the message without serde_json's own " at line 1 column 2"
+    let message = e.to_string();
+    let message = message.split(" at line ").next().unwrap_or_default();
+    Err(format!(
+        "line {}, column {}: {message}",
+        e.line(),
+        e.column()
+    ))
+}
+
+fn toml(text: &str) -> Result<(), String> {
+    let Err(e) = text.parse::<toml::Table>() else {
+        return Ok(());
+    };
+    // toml puts what it expected on lines of its own
+    let message = e.message().trim_end().replace('\n', "; ");
+    match e.span() {
+        Some(span) => Err(format!("{}: {message}", position(text, span.start))),
+        None => Err(message),
+    }
+}
+
+/// `line L, column C` of a byte offset, both 1-based, columns in characters.
+fn position(text: &str, offset: usize) -> String {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    format!("line {line}, column {column}")
+}
+
+/// Checks that XML is well-formed, DTDs allowed.
+fn xml(text: &str) -> Result<(), String> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..roxmltree::ParsingOptions::default()
+    };
+    let Err(e) = roxmltree::Document::parse_with_options(text, options) else {
+        return Ok(());
+    };
+    // the message without roxmltree's own " at 1:2"
+    let pos = e.pos();
+    let message = e.to_string();
+    let message = message
+        .strip_suffix(&format!(" at {pos}"))
+        .unwrap_or(&message);
+    Err(format!("line {}, column {}: {message}", pos.row, pos.col))
+}
+
+#[cfg(test)]
+#[path = "validate_tests.rs"]
+mod tests;
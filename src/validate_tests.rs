@@ -0,0 +1,103 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::detect::ContentType::{self, Csv, Json, Python, Toml, Tsv, Xml, Yaml};
+use crate::validate::*;
+
+fn err(text: &str, content_type: ContentType) -> String {
+    check(text, &content_type).unwrap().unwrap_err()
+}
+
+fn ok(text: &str, content_type: ContentType) -> bool {
+    check(text, &content_type) == Some(Ok(()))
+}
+
+#[test]
+fn test_json() {
+    assert!(ok("{\"a\": [1, 2]}\n", Json));
+    // smart quotes from a wiki
+    assert_eq!(
+        err("{\n  \u{201c}a\u{201d}: 1\n}", Json),
+        "line 2, column 3: key must be a string"
+    );
+    assert_eq!(
+        err("{\"a\": [1, 2", Json),
+        "line 1, column 11: EOF while parsing a list"
+    );
+}
+
+#[test]
+fn test_toml() {
+    assert!(ok("[server]\nport = 80\n", Toml));
+    assert_eq!(
+        err("name = \"web\"\nport = \n", Toml),
+        "line 2, column 8: invalid string; expected `\"`, `'`"
+    );
+}
+
+#[test]
+fn test_yaml() {
+    assert!(ok("a:\n  - 1\n", Yaml));
+    // streams of several documents, anchors, merge keys and tags
+    assert!(ok(
+        "kind: Service\n---\nkind: Deployment\n",
+        ContentType::Kubernetes {
+            kind: String::new()
+        }
+    ));
+    assert!(ok(
+        "x-base: &base\n  image: app\nweb:\n  <<: *base\n  tag: !Ref name\n",
+        Yaml
+    ));
+    assert_eq!(
+        err("a: 1\na: 2\n", Yaml),
+        "line 1, column 1: duplicate entry with key \"a\""
+    );
+}
+
+#[test]
+fn test_csv() {
+    assert!(ok("a,b\n1,2\n", Csv));
+    assert_eq!(
+        err("a\tb\n1\n", Tsv),
+        "line 2: expected 2 fields like the header, found 1"
+    );
+}
+
+#[test]
+fn test_xml() {
+    let valid = "<?xml version=\"1.0\"?>\n<!DOCTYPE note [<!ENTITY x \"y\">]>\n\
+        <!-- hi -->\n<note id='1' lang=\"en\">a &amp; b &#169; &#xA9;\
+        <![CDATA[<raw>]]><br/></note>\n";
+    assert!(ok(valid, Xml));
+    assert!(ok(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+        ContentType::Svg
+    ));
+
+    assert_eq!(
+        err("<a>\n  <b></a>", Xml),
+        "line 2, column 6: expected 'b' tag, not 'a'"
+    );
+    assert_eq!(
+        err("<a x=1/>", Xml),
+        "line 1, column 6: expected a quote not '1'"
+    );
+    assert_eq!(
+        err("<a>Tom & Jerry</a>", Xml),
+        "line 1, column 8: malformed entity reference"
+    );
+    assert_eq!(
+        err("<a/>\ntrailing", Xml),
+        "line 2, column 1: unknown token"
+    );
+    assert_eq!(
+        err("<a>\n<b>", Xml),
+        "line 1, column 1: the root node was opened but never closed"
+    );
+}
+
+#[test]
+fn test_unchecked_types() {
+    assert_eq!(check("def f(:", &Python), None);
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+// YAML for --convert and --validate, through serde_yaml_ng. Documents are
+// read as JSON values, which is what the rest of c2f converts between.
+
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use serde_yaml_ng::Value as Yaml;
+
+/// Parses a single YAML document into a JSON value, with `<<` merge keys
+/// applied. Data JSON can't hold, like tagged values or non-scalar keys, is
+/// an error.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut documents = read(text)?;
+    if documents.len() > 1 {
+        return Err(
+            "several YAML documents can't be converted, save them one at a time".to_string(),
+        );
+    }
+    let mut document = documents.pop().unwrap_or(Yaml::Null);
+    document.apply_merge().map_err(|e| message(&e))?;
+    to_json(document)
+}
+
+/// Checks that `text` is a YAML stream, of any number of documents.
+pub fn check(text: &str) -> Result<(), String> {
+    read(text).map(|_| ())
+}
+
+fn read(text: &str) -> Result<Vec<Yaml>, String> {
+    serde_yaml_ng::Deserializer::from_str(text)
+        .map(|document| Yaml::deserialize(document).map_err(|e| message(&e)))
+        .collect()
+}
+
+/// The error as `line L, column C: message`, like the other formats.
+fn message(e: &serde_yaml_ng::Error) -> String {
+    let text = e.to_string();
+    let Some(location) = e.location() else {
+        return text;
+    };
+    let suffix = format!(" at line {} column {}", location.line(), location.column());
+    let text = text.replacen(&suffix, "", 1);
+    format!(
+        "line {}, column {}: {text}",
+        location.line(),
+        location.column()
+    )
+}
+
+fn to_json(value: Yaml) -> Result<Value, String> {
+    Ok(match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::Number(i.into())
+            } else if let Some(u) = n.as_u64() {
+                Value::Number(u.into())
+            } else {
+                let f = n.as_f64().unwrap_or(f64::NAN);
+                Value::Number(
+                    Number::from_f64(f).ok_or_else(|| format!("{n} has no JSON equivalent"))?,
+                )
+            }
+        }
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => {
+            Value::Array(items.into_iter().map(to_json).collect::<Result<_, _>>()?)
+        }
+        Yaml::Mapping(mapping) => {
+            let mut map = Map::new();
+            for (key, value) in mapping {
+                let key = match key {
+                    Yaml::String(s) => s,
+                    Yaml::Null => "null".to_string(),
+                    Yaml::Bool(b) => b.to_string(),
+                    Yaml::Number(n) => n.to_string(),
+                    _ => return Err("mappings as keys can't be converted".to_string()),
+                };
+                map.insert(key, to_json(value)?);
+            }
+            Value::Object(map)
+        }
+        Yaml::Tagged(tagged) => {
+            return Err(format!("the YAML tag {} can't be converted", tagged.tag))
+        }
+    })
+}
+
+/// Writes a JSON value as a YAML document.
+pub fn to_string(value: &Value) -> Result<String, String> {
+    serde_yaml_ng::to_string(value).map_err(|e| format!("Can't write YAML: {e}"))
+}
+
+#[cfg(test)]
+#[path = "yaml_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+// Copyright (c) 2025 balintb - https://github.com/balintb/c2f
+// Licensed under the MIT License
+
+use crate::yaml::*;
+use serde_json::json;
+
+#[test]
+fn test_parse() {
+    let text = "\
+# service config
+name: web
+port: 8080
+tags: [a, b]
+servers:
+- host: one
+nested:
+  key: value # trailing comment
+";
+    assert_eq!(
+        parse(text).unwrap(),
+        json!({
+            "name": "web",
+            "port": 8080,
+            "tags": ["a", "b"],
+            "servers": [{"host": "one"}],
+            "nested": {"key": "value"}
+        })
+    );
+    // key order is kept
+    let value = parse("b: 1\na: 2\n").unwrap();
+    let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["b", "a"]);
+}
+
+#[test]
+fn test_block_scalars_keep_comment_lines() {
+    // GitHub Actions steps: `#` inside a block scalar is content
+    let text = "run: |\n  # install\n  npm ci\n";
+    assert_eq!(parse(text).unwrap(), json!({"run": "# install\nnpm ci\n"}));
+    let round_trip = to_string(&parse(text).unwrap()).unwrap();
+    assert_eq!(parse(&round_trip).unwrap(), parse(text).unwrap());
+}
+
+#[test]
+fn test_merge_keys() {
+    let text = "\
+base: &base
+  image: app
+  port: 80
+web:
+  <<: *base
+  port: 8080
+";
+    assert_eq!(
+        parse(text).unwrap()["web"],
+        json!({"port": 8080, "image": "app"})
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("a: 1\n---\nb: 2\n").unwrap_err(),
+        "several YAML documents can't be converted, save them one at a time"
+    );
+    assert_eq!(
+        parse("ref: !Ref Bucket\n").unwrap_err(),
+        "the YAML tag !Ref can't be converted"
+    );
+    assert_eq!(
+        parse("a: 1\na: 2\n").unwrap_err(),
+        "line 1, column 1: duplicate entry with key \"a\""
+    );
+}
+
+#[test]
+fn test_check() {
+    // multi-document streams, anchors and tags are all valid YAML
+    assert_eq!(check("kind: A\n---\nkind: B\n"), Ok(()));
+    assert_eq!(check("a: &x 1\nb: *x\nc: !Ref d\n"), Ok(()));
+    assert!(check("a: [1, 2\n").unwrap_err().starts_with("line "));
+}
+
+#[test]
+fn test_to_string() {
+    let value = json!({"name": "web", "tags": ["a"], "version": "1.0"});
+    let text = to_string(&value).unwrap();
+    assert_eq!(text, "name: web\ntags:\n- a\nversion: '1.0'\n");
+    assert_eq!(parse(&text).unwrap(), value);
+}
@@ -0,0 +1,14 @@
+static size_t count_words(const char *s)
+{
+    size_t n = 0;
+    int in_word = 0;
+    for (; *s; s++) {
+        if (isspace((unsigned char)*s)) {
+            in_word = 0;
+        } else if (!in_word) {
+            in_word = 1;
+            n++;
+        }
+    }
+    return n;
+}
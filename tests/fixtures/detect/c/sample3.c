@@ -0,0 +1,14 @@
+static int parse_port(const char *s, uint16_t *out)
+{
+    char *end;
+    long v;
+
+    errno = 0;
+    v = strtol(s, &end, 10);
+    if (errno != 0 || *end != '\0' || v < 1 || v > 65535) {
+        fprintf(stderr, "invalid port: %s\n", s);
+        return -1;
+    }
+    *out = (uint16_t)v;
+    return 0;
+}
@@ -0,0 +1,32 @@
+#include <stdlib.h>
+#include <string.h>
+
+struct buffer {
+    char *data;
+    size_t len;
+    size_t cap;
+};
+
+int buffer_append(struct buffer *b, const char *src, size_t n)
+{
+    if (b->len + n > b->cap) {
+        size_t cap = b->cap ? b->cap * 2 : 64;
+        while (cap < b->len + n)
+            cap *= 2;
+        char *p = realloc(b->data, cap);
+        if (!p)
+            return -1;
+        b->data = p;
+        b->cap = cap;
+    }
+    memcpy(b->data + b->len, src, n);
+    b->len += n;
+    return 0;
+}
+
+void buffer_free(struct buffer *b)
+{
+    free(b->data);
+    b->data = NULL;
+    b->len = b->cap = 0;
+}
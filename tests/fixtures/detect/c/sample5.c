@@ -0,0 +1,16 @@
+typedef struct node {
+    int key;
+    struct node *next;
+} node_t;
+
+node_t *list_reverse(node_t *head)
+{
+    node_t *prev = NULL;
+    while (head) {
+        node_t *next = head->next;
+        head->next = prev;
+        prev = head;
+        head = next;
+    }
+    return prev;
+}
@@ -0,0 +1,9 @@
+#include <iostream>
+#include <map>
+
+int main() {
+    std::map<std::string, int> ages{{"alice", 30}, {"bob", 25}};
+    for (const auto& [name, age] : ages) {
+        std::cout << name << ": " << age << "\n";
+    }
+}
@@ -0,0 +1,11 @@
+class Timer {
+public:
+    explicit Timer(std::string name) : name_(std::move(name)), start_(std::chrono::steady_clock::now()) {}
+    ~Timer() {
+        auto elapsed = std::chrono::steady_clock::now() - start_;
+        std::cerr << name_ << " took " << elapsed.count() << "ns" << std::endl;
+    }
+private:
+    std::string name_;
+    std::chrono::steady_clock::time_point start_;
+};
@@ -0,0 +1,14 @@
+#include <algorithm>
+#include <string>
+#include <vector>
+
+std::vector<std::string> split(const std::string& s, char delim) {
+    std::vector<std::string> parts;
+    std::string::size_type start = 0;
+    for (auto pos = s.find(delim); pos != std::string::npos; pos = s.find(delim, start)) {
+        parts.emplace_back(s.substr(start, pos - start));
+        start = pos + 1;
+    }
+    parts.emplace_back(s.substr(start));
+    return parts;
+}
@@ -0,0 +1,18 @@
+template <typename T>
+class RingBuffer {
+public:
+    explicit RingBuffer(std::size_t capacity) : data_(capacity) {}
+
+    void push(const T& value) {
+        data_[head_] = value;
+        head_ = (head_ + 1) % data_.size();
+        if (size_ < data_.size()) ++size_;
+    }
+
+    std::size_t size() const noexcept { return size_; }
+
+private:
+    std::vector<T> data_;
+    std::size_t head_ = 0;
+    std::size_t size_ = 0;
+};
@@ -0,0 +1,19 @@
+namespace net {
+
+Connection::~Connection() {
+    std::lock_guard<std::mutex> lock(mutex_);
+    if (socket_ >= 0) {
+        ::close(socket_);
+    }
+}
+
+std::unique_ptr<Connection> Connection::open(const std::string& host, int port) {
+    auto conn = std::make_unique<Connection>();
+    if (!conn->connect(host, port)) {
+        std::cerr << "connect failed: " << host << ':' << port << std::endl;
+        return nullptr;
+    }
+    return conn;
+}
+
+}  // namespace net
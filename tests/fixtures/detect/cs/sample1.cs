@@ -0,0 +1,7 @@
+public static class StringExtensions
+{
+    public static bool IsBlank(this string? value)
+    {
+        return string.IsNullOrWhiteSpace(value);
+    }
+}
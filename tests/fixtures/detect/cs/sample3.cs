@@ -0,0 +1,23 @@
+using System.Collections.Generic;
+using System.Linq;
+
+namespace Inventory.Services
+{
+    public class StockService
+    {
+        private readonly IRepository<Item> _items;
+
+        public StockService(IRepository<Item> items)
+        {
+            _items = items;
+        }
+
+        public IEnumerable<Item> LowStock(int threshold)
+        {
+            return _items.GetAll()
+                .Where(i => i.Quantity < threshold)
+                .OrderBy(i => i.Name)
+                .ToList();
+        }
+    }
+}
@@ -0,0 +1,15 @@
+[ApiController]
+[Route("api/[controller]")]
+public class OrdersController : ControllerBase
+{
+    [HttpGet("{id}")]
+    public async Task<ActionResult<OrderDto>> Get(int id)
+    {
+        var order = await _context.Orders.FindAsync(id);
+        if (order == null)
+        {
+            return NotFound();
+        }
+        return Ok(_mapper.Map<OrderDto>(order));
+    }
+}
@@ -0,0 +1,11 @@
+public record Point(double X, double Y)
+{
+    public double DistanceTo(Point other)
+    {
+        var dx = X - other.X;
+        var dy = Y - other.Y;
+        return Math.Sqrt(dx * dx + dy * dy);
+    }
+
+    public override string ToString() => $"({X:F2}, {Y:F2})";
+}
@@ -0,0 +1,10 @@
+class Counter {
+  int _value = 0;
+  int get value => _value;
+  void increment() => _value++;
+}
+
+void main() {
+  final c = Counter()..increment();
+  print('Value: ${c.value}');
+}
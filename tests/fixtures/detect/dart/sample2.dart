@@ -0,0 +1,5 @@
+Future<void> main() async {
+  final prefs = await SharedPreferences.getInstance();
+  final name = prefs.getString('name') ?? 'guest';
+  print('Hello $name');
+}
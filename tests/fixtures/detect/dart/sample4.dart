@@ -0,0 +1,11 @@
+import 'dart:convert';
+import 'package:http/http.dart' as http;
+
+Future<List<String>> fetchTitles(Uri uri) async {
+  final response = await http.get(uri);
+  if (response.statusCode != 200) {
+    throw Exception('Request failed: ${response.statusCode}');
+  }
+  final List<dynamic> body = jsonDecode(response.body);
+  return body.map((e) => e['title'] as String).toList();
+}
@@ -0,0 +1,11 @@
+final class User {
+  final String name;
+  final int? age;
+
+  User(this.name, {this.age});
+
+  factory User.fromJson(Map<String, dynamic> json) =>
+      User(json['name'] as String, age: json['age'] as int?);
+
+  String get label => age == null ? name : '$name ($age)';
+}
@@ -0,0 +1,7 @@
+defmodule Greeter do
+  def hello(name \\ "world") do
+    "Hello, #{name}!"
+  end
+end
+
+IO.puts(Greeter.hello())
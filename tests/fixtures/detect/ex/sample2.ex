@@ -0,0 +1,6 @@
+def sum_list([]), do: 0
+def sum_list([head | tail]), do: head + sum_list(tail)
+
+[1, 2, 3]
+|> Enum.map(&(&1 * &1))
+|> Enum.sum()
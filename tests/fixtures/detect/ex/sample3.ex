@@ -0,0 +1,15 @@
+defmodule Shipping.RateCalculator do
+  @moduledoc "Quotes shipping rates by weight and zone."
+
+  @base_rates %{domestic: 4.99, europe: 12.5, world: 19.0}
+
+  def quote(%{weight_kg: kg, zone: zone}) when kg > 0 do
+    base = Map.fetch!(@base_rates, zone)
+    {:ok, Float.round(base + kg * surcharge(zone), 2)}
+  end
+
+  def quote(_parcel), do: {:error, :invalid_parcel}
+
+  defp surcharge(:domestic), do: 0.8
+  defp surcharge(_zone), do: 2.25
+end
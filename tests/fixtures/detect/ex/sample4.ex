@@ -0,0 +1,7 @@
+def handle_call({:get, key}, _from, state) do
+  {:reply, Map.get(state, key), state}
+end
+
+def handle_cast({:put, key, value}, state) do
+  {:noreply, Map.put(state, key, value)}
+end
@@ -0,0 +1,10 @@
+case File.read(path) do
+  {:ok, contents} ->
+    contents
+    |> String.split("\n", trim: true)
+    |> Enum.map(&String.trim/1)
+
+  {:error, reason} ->
+    Logger.error("could not read #{path}: #{inspect(reason)}")
+    []
+end
@@ -0,0 +1,10 @@
+package config
+
+type Options struct {
+	Verbose bool
+	Workers int
+}
+
+func Default() Options {
+	return Options{Workers: 4}
+}
@@ -0,0 +1,30 @@
+package worker
+
+import (
+	"context"
+	"sync"
+)
+
+type Pool struct {
+	jobs chan func()
+	wg   sync.WaitGroup
+}
+
+func New(ctx context.Context, n int) *Pool {
+	p := &Pool{jobs: make(chan func())}
+	for i := 0; i < n; i++ {
+		p.wg.Add(1)
+		go func() {
+			defer p.wg.Done()
+			for {
+				select {
+				case job := <-p.jobs:
+					job()
+				case <-ctx.Done():
+					return
+				}
+			}
+		}()
+	}
+	return p
+}
@@ -0,0 +1,7 @@
+import Data.Char (toUpper)
+
+shout :: String -> String
+shout = map toUpper
+
+main :: IO ()
+main = putStrLn (shout "hello")
@@ -0,0 +1,8 @@
+data Tree a = Leaf | Node (Tree a) a (Tree a)
+
+insert :: Ord a => a -> Tree a -> Tree a
+insert x Leaf = Node Leaf x Leaf
+insert x t@(Node l v r)
+  | x < v = Node (insert x l) v r
+  | x > v = Node l v (insert x r)
+  | otherwise = t
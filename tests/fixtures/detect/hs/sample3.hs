@@ -0,0 +1,11 @@
+module Inventory (restock, lowItems) where
+
+import qualified Data.Map.Strict as M
+
+type Stock = M.Map String Int
+
+restock :: String -> Int -> Stock -> Stock
+restock item n = M.insertWith (+) item n
+
+lowItems :: Int -> Stock -> [String]
+lowItems limit = M.keys . M.filter (< limit)
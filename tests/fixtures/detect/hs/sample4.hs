@@ -0,0 +1,14 @@
+data Token
+  = TNum Integer
+  | TPlus
+  | TTimes
+  deriving (Show)
+
+tokenize :: String -> [Token]
+tokenize [] = []
+tokenize (c:cs)
+  | c == '+' = TPlus : tokenize cs
+  | c == '*' = TTimes : tokenize cs
+  | c `elem` ['0'..'9'] = let (ds, rest) = span (`elem` ['0'..'9']) (c:cs)
+                          in TNum (read ds) : tokenize rest
+  | otherwise = tokenize cs
@@ -0,0 +1,7 @@
+safeDiv :: Int -> Int -> Either String Int
+safeDiv _ 0 = Left "divide by zero"
+safeDiv a b = Right (a `div` b)
+
+average :: [Int] -> Maybe Double
+average [] = Nothing
+average xs = Just (fromIntegral (sum xs) / fromIntegral (length xs))
@@ -0,0 +1,11 @@
+public class Counter {
+    private int value;
+
+    public synchronized void increment() {
+        value++;
+    }
+
+    public int get() {
+        return value;
+    }
+}
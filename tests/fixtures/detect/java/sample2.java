@@ -0,0 +1,7 @@
+List<String> names = new ArrayList<>();
+for (User user : users) {
+    if (user.isActive()) {
+        names.add(user.getName());
+    }
+}
+System.out.println(String.join(", ", names));
@@ -0,0 +1,15 @@
+package com.example.billing;
+
+import java.math.BigDecimal;
+import java.util.List;
+
+public class InvoiceCalculator {
+    private static final BigDecimal VAT = new BigDecimal("0.20");
+
+    public BigDecimal total(List<LineItem> items) {
+        BigDecimal net = items.stream()
+                .map(i -> i.unitPrice().multiply(BigDecimal.valueOf(i.quantity())))
+                .reduce(BigDecimal.ZERO, BigDecimal::add);
+        return net.add(net.multiply(VAT));
+    }
+}
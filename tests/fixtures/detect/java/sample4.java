@@ -0,0 +1,10 @@
+try (BufferedReader reader = Files.newBufferedReader(path, StandardCharsets.UTF_8)) {
+    String line;
+    while ((line = reader.readLine()) != null) {
+        if (!line.isBlank()) {
+            lines.add(line.strip());
+        }
+    }
+} catch (IOException e) {
+    throw new UncheckedIOException("Failed to read " + path, e);
+}
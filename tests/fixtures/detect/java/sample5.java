@@ -0,0 +1,20 @@
+public interface Shape {
+    double area();
+
+    default String describe() {
+        return getClass().getSimpleName() + " with area " + String.format("%.2f", area());
+    }
+}
+
+public final class Circle implements Shape {
+    private final double radius;
+
+    public Circle(double radius) {
+        this.radius = radius;
+    }
+
+    @Override
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+}
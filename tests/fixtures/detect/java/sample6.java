@@ -0,0 +1,4 @@
+Map<String, Long> counts = words.stream()
+        .filter(w -> !w.isEmpty())
+        .collect(Collectors.groupingBy(Function.identity(), Collectors.counting()));
+counts.forEach((word, n) -> System.out.println(word + ": " + n));
@@ -0,0 +1,12 @@
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+async function retry(fn, attempts = 3) {
+  for (let i = 0; i < attempts; i++) {
+    try {
+      return await fn();
+    } catch (err) {
+      await sleep(2 ** i * 100);
+    }
+  }
+  throw new Error('gave up');
+}
@@ -0,0 +1,5 @@
+button.addEventListener('click', () => {
+  const count = Number(label.textContent) + 1;
+  label.textContent = count;
+  localStorage.setItem('count', count);
+});
@@ -0,0 +1,8 @@
+document.querySelectorAll('.tab').forEach((tab) => {
+  tab.addEventListener('click', () => {
+    document.querySelector('.tab.active')?.classList.remove('active');
+    tab.classList.add('active');
+    const panel = document.getElementById(tab.dataset.target);
+    panel.scrollIntoView({ behavior: 'smooth' });
+  });
+});
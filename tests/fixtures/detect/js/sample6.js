@@ -0,0 +1,9 @@
+module.exports = {
+  content: ['./src/**/*.{html,js}'],
+  theme: {
+    extend: {
+      colors: { brand: '#ff6600' },
+    },
+  },
+  plugins: [],
+};
@@ -0,0 +1,7 @@
+const List = ({ items }) => (
+  <ul>
+    {items.map((item) => (
+      <li key={item.id}>{item.label}</li>
+    ))}
+  </ul>
+);
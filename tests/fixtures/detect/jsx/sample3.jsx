@@ -0,0 +1,12 @@
+import { useState } from 'react';
+
+export default function SearchBox({ onSearch }) {
+  const [query, setQuery] = useState('');
+
+  return (
+    <form onSubmit={(e) => { e.preventDefault(); onSearch(query); }}>
+      <input value={query} onChange={(e) => setQuery(e.target.value)} placeholder="Search" />
+      <button type="submit">Go</button>
+    </form>
+  );
+}
@@ -0,0 +1,11 @@
+function Avatar({ user, size = 32 }) {
+  return (
+    <img
+      className="avatar"
+      src={user.avatarUrl}
+      alt={user.name}
+      width={size}
+      height={size}
+    />
+  );
+}
@@ -0,0 +1,10 @@
+fun fibonacci(n: Int): Long {
+    var a = 0L
+    var b = 1L
+    repeat(n) {
+        val next = a + b
+        a = b
+        b = next
+    }
+    return a
+}
@@ -0,0 +1,6 @@
+data class Point(val x: Int, val y: Int) {
+    operator fun plus(other: Point) = Point(x + other.x, y + other.y)
+}
+
+val points = listOf(Point(1, 2), Point(3, 4))
+println(points.reduce { acc, p -> acc + p })
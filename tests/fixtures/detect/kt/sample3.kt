@@ -0,0 +1,13 @@
+data class Todo(val id: Long, val title: String, val done: Boolean = false)
+
+class TodoRepository {
+    private val items = mutableListOf<Todo>()
+
+    fun add(title: String): Todo {
+        val todo = Todo(id = items.size + 1L, title = title)
+        items += todo
+        return todo
+    }
+
+    fun pending(): List<Todo> = items.filter { !it.done }
+}
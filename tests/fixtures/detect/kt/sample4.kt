@@ -0,0 +1,7 @@
+suspend fun loadProfile(id: String): Profile = withContext(Dispatchers.IO) {
+    val response = client.get("https://api.example.com/profiles/$id")
+    if (!response.status.isSuccess()) {
+        throw IllegalStateException("HTTP ${response.status.value}")
+    }
+    response.body<Profile>()
+}
@@ -0,0 +1,9 @@
+fun main() {
+    val words = listOf("apple", "banana", "cherry", "avocado")
+    val byLetter = words.groupBy { it.first() }
+    for ((letter, group) in byLetter) {
+        println("$letter -> ${group.joinToString()}")
+    }
+    val longest = words.maxByOrNull { it.length } ?: return
+    println("longest: $longest")
+}
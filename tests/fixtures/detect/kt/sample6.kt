@@ -0,0 +1,11 @@
+sealed interface UiState {
+    object Loading : UiState
+    data class Ready(val items: List<String>) : UiState
+    data class Failed(val message: String) : UiState
+}
+
+fun render(state: UiState): String = when (state) {
+    is UiState.Loading -> "Loading…"
+    is UiState.Ready -> state.items.joinToString(separator = "\n")
+    is UiState.Failed -> "Error: ${state.message}"
+}
@@ -0,0 +1,9 @@
+local function split(str, sep)
+  local parts = {}
+  for part in string.gmatch(str, "([^" .. sep .. "]+)") do
+    table.insert(parts, part)
+  end
+  return parts
+end
+
+print(#split("a,b,c", ","))
@@ -0,0 +1,6 @@
+function love.update(dt)
+  player.x = player.x + player.speed * dt
+  if player.x > 800 then
+    player.x = 0
+  end
+end
@@ -0,0 +1,11 @@
+local M = {}
+
+function M.setup(opts)
+  opts = opts or {}
+  vim.keymap.set("n", "<leader>ff", function()
+    require("telescope.builtin").find_files()
+  end, { desc = "Find files" })
+  vim.opt.number = opts.number ~= false
+end
+
+return M
@@ -0,0 +1,15 @@
+local Player = {}
+Player.__index = Player
+
+function Player.new(x, y)
+  local self = setmetatable({}, Player)
+  self.x, self.y = x, y
+  self.speed = 120
+  return self
+end
+
+function Player:update(dt)
+  if love.keyboard.isDown("right") then
+    self.x = self.x + self.speed * dt
+  end
+end
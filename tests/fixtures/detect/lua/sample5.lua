@@ -0,0 +1,5 @@
+for i, item in ipairs(items) do
+  if item.count > 0 then
+    print(string.format("%d: %s x%d", i, item.name, item.count))
+  end
+end
@@ -0,0 +1,8 @@
+{ pkgs, ... }:
+{
+  home.packages = with pkgs; [ ripgrep fd bat ];
+  programs.git = {
+    enable = true;
+    userName = "Jane";
+  };
+}
@@ -0,0 +1,4 @@
+let
+  pkgs = import <nixpkgs> { };
+in
+pkgs.python3.withPackages (ps: with ps; [ numpy requests ])
@@ -0,0 +1,16 @@
+{ lib, buildGoModule, fetchFromGitHub }:
+
+buildGoModule rec {
+  pname = "tally";
+  version = "0.4.2";
+
+  src = fetchFromGitHub {
+    owner = "tally-dev";
+    repo = "tally";
+    tag = "v${version}";
+    hash = lib.fakeHash;
+  };
+
+  vendorHash = null;
+  meta.license = lib.licenses.mit;
+}
@@ -0,0 +1,12 @@
+{ config, pkgs, ... }:
+
+{
+  time.timeZone = "Europe/Lisbon";
+  services.openssh.settings.PasswordAuthentication = false;
+  programs.zsh.enable = true;
+  boot.loader.systemd-boot.enable = true;
+  fileSystems."/data" = {
+    device = "/dev/disk/by-label/data";
+    fsType = "ext4";
+  };
+}
@@ -0,0 +1,8 @@
+<?php
+function slugify(string $text): string
+{
+    $text = strtolower(trim($text));
+    return preg_replace('/[^a-z0-9]+/', '-', $text);
+}
+
+echo slugify("Hello World!");
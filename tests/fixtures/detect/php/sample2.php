@@ -0,0 +1,7 @@
+<?php
+session_start();
+if (!isset($_SESSION['user_id'])) {
+    header('Location: login.php');
+    exit;
+}
+$user = $db->query("SELECT name FROM users WHERE id = " . (int)$_SESSION['user_id'])->fetch();
@@ -0,0 +1,14 @@
+<?php
+
+declare(strict_types=1);
+
+final class SlugGenerator
+{
+    public function __construct(private int $maxLength = 60) {}
+
+    public function make(string $title): string
+    {
+        $slug = strtolower(trim(preg_replace('/[^A-Za-z0-9]+/', '-', $title), '-'));
+        return substr($slug, 0, $this->maxLength);
+    }
+}
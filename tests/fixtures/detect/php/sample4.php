@@ -0,0 +1,7 @@
+<?php
+$pdo = new PDO('mysql:host=localhost;dbname=shop', $user, $pass);
+$stmt = $pdo->prepare('SELECT * FROM products WHERE price < :max');
+$stmt->execute(['max' => $max]);
+foreach ($stmt->fetchAll(PDO::FETCH_ASSOC) as $row) {
+    echo htmlspecialchars($row['name']) . "\n";
+}
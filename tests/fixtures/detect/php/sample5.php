@@ -0,0 +1,5 @@
+<ul>
+<?php foreach ($items as $item): ?>
+    <li><?= htmlspecialchars($item['label']) ?></li>
+<?php endforeach; ?>
+</ul>
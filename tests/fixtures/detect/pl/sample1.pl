@@ -0,0 +1,8 @@
+use strict;
+use warnings;
+
+my @lines = <STDIN>;
+chomp @lines;
+my %seen;
+my @unique = grep { !$seen{$_}++ } @lines;
+print "$_\n" for @unique;
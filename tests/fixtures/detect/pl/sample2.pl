@@ -0,0 +1,5 @@
+my $str = "2024-05-01";
+if ($str =~ /^(\d{4})-(\d{2})-(\d{2})$/) {
+    my ($y, $m, $d) = ($1, $2, $3);
+    print "year=$y month=$m day=$d\n";
+}
@@ -0,0 +1,13 @@
+#!/usr/bin/perl
+use strict;
+use warnings;
+
+my %count;
+while (my $line = <STDIN>) {
+    chomp $line;
+    next unless $line =~ /^(\S+)\s/;
+    $count{$1}++;
+}
+for my $ip (sort { $count{$b} <=> $count{$a} } keys %count) {
+    printf "%-15s %d\n", $ip, $count{$ip};
+}
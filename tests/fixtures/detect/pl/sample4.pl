@@ -0,0 +1,13 @@
+sub read_config {
+    my ($path) = @_;
+    open(my $fh, '<', $path) or die "Cannot open $path: $!";
+    my %cfg;
+    while (<$fh>) {
+        s/#.*//;
+        next if /^\s*$/;
+        my ($k, $v) = split /\s*=\s*/, $_, 2;
+        $cfg{$k} = $v;
+    }
+    close $fh;
+    return \%cfg;
+}
@@ -0,0 +1,5 @@
+my @files = grep { -f && /\.txt$/ } glob("$dir/*");
+foreach my $file (@files) {
+    my $size = -s $file;
+    print "$file: $size bytes\n";
+}
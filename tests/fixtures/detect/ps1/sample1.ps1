@@ -0,0 +1,5 @@
+$logs = Get-ChildItem -Path C:\Logs -Filter *.log
+foreach ($log in $logs) {
+    $errors = Select-String -Path $log.FullName -Pattern "ERROR"
+    Write-Host "$($log.Name): $($errors.Count) errors"
+}
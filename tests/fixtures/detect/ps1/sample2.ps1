@@ -0,0 +1,5 @@
+function Test-Port {
+    param([string]$ComputerName, [int]$Port = 443)
+    $client = New-Object System.Net.Sockets.TcpClient
+    try { $client.Connect($ComputerName, $Port); $true } catch { $false } finally { $client.Dispose() }
+}
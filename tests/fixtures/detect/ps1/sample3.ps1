@@ -0,0 +1,10 @@
+[CmdletBinding()]
+param(
+    [string]$LogFolder = "C:\Logs",
+    [int]$KeepDays = 14
+)
+
+$limit = (Get-Date).AddDays(-$KeepDays)
+Get-ChildItem $LogFolder -Filter *.log |
+    Where-Object LastWriteTime -lt $limit |
+    ForEach-Object { Remove-Item $_.FullName; Write-Verbose "Deleted $($_.Name)" }
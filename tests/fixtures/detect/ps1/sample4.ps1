@@ -0,0 +1,4 @@
+$services = Get-Service | Where-Object { $_.Status -eq 'Running' }
+foreach ($svc in $services) {
+    Write-Host "$($svc.Name): $($svc.DisplayName)"
+}
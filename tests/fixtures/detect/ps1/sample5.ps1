@@ -0,0 +1,7 @@
+function Get-DiskUsage {
+    [CmdletBinding()]
+    param([string]$ComputerName = $env:COMPUTERNAME)
+
+    Get-CimInstance -ClassName Win32_LogicalDisk -ComputerName $ComputerName |
+        Select-Object DeviceID, @{Name = 'FreeGB'; Expression = { [math]::Round($_.FreeSpace / 1GB, 1) }}
+}
@@ -0,0 +1,7 @@
+def chunked(items, size):
+    for i in range(0, len(items), size):
+        yield items[i:i + size]
+
+
+for batch in chunked(list(range(20)), 5):
+    print(sum(batch))
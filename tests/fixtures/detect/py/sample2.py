@@ -0,0 +1,7 @@
+class Cache:
+    def __init__(self, capacity=128):
+        self.capacity = capacity
+        self._data = {}
+
+    def get(self, key, default=None):
+        return self._data.get(key, default)
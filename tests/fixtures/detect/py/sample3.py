@@ -0,0 +1,13 @@
+from dataclasses import dataclass, field
+from typing import Optional
+
+
+@dataclass
+class Task:
+    title: str
+    priority: int = 0
+    tags: list[str] = field(default_factory=list)
+    parent: Optional["Task"] = None
+
+    def is_urgent(self) -> bool:
+        return self.priority >= 3 or "urgent" in self.tags
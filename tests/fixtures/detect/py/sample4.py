@@ -0,0 +1,18 @@
+import argparse
+import json
+import sys
+
+
+def main(argv=None):
+    parser = argparse.ArgumentParser(description="Pretty-print JSON")
+    parser.add_argument("path", nargs="?", default="-")
+    args = parser.parse_args(argv)
+
+    stream = sys.stdin if args.path == "-" else open(args.path)
+    with stream:
+        data = json.load(stream)
+    print(json.dumps(data, indent=2, sort_keys=True))
+
+
+if __name__ == "__main__":
+    main()
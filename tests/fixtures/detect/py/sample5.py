@@ -0,0 +1,8 @@
+df = pd.read_csv("sales.csv", parse_dates=["date"])
+monthly = (
+    df.groupby(df["date"].dt.to_period("M"))["revenue"]
+    .sum()
+    .reset_index()
+)
+for row in monthly.itertuples():
+    print(f"{row.date}: {row.revenue:,.2f}")
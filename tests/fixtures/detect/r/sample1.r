@@ -0,0 +1,5 @@
+scores <- c(82, 91, 77, 68, 95)
+names(scores) <- c("ann", "bob", "cat", "dan", "eve")
+passed <- scores[scores >= 75]
+print(mean(passed))
+barplot(scores, col = "steelblue")
@@ -0,0 +1,3 @@
+df <- read.csv("iris.csv")
+agg <- aggregate(Sepal.Length ~ Species, data = df, FUN = mean)
+write.csv(agg, "means.csv", row.names = FALSE)
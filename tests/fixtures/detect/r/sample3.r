@@ -0,0 +1,11 @@
+library(dplyr)
+library(ggplot2)
+
+df <- read.csv("measurements.csv")
+summary_df <- df %>%
+  group_by(site) %>%
+  summarise(mean_temp = mean(temp, na.rm = TRUE), n = n())
+
+ggplot(summary_df, aes(x = site, y = mean_temp)) +
+  geom_col() +
+  theme_minimal()
@@ -0,0 +1,4 @@
+fit <- lm(mpg ~ wt + hp, data = mtcars)
+summary(fit)
+plot(fitted(fit), resid(fit))
+abline(h = 0, col = "red")
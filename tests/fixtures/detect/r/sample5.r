@@ -0,0 +1,8 @@
+normalize <- function(x) {
+  rng <- range(x, na.rm = TRUE)
+  (x - rng[1]) / (rng[2] - rng[1])
+}
+
+scores <- c(12, 45, 7, 30)
+names(scores) <- c("a", "b", "c", "d")
+print(normalize(scores))
@@ -0,0 +1,4 @@
+data <- read.csv("survey.csv", stringsAsFactors = FALSE)
+data$age_group <- cut(data$age, breaks = c(0, 30, 50, Inf))
+table(data$age_group)
+aggregate(score ~ age_group, data = data, FUN = mean)
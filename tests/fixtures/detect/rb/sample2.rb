@@ -0,0 +1,14 @@
+class Stack
+  def initialize
+    @items = []
+  end
+
+  def push(item)
+    @items << item
+    self
+  end
+
+  def pop
+    @items.pop
+  end
+end
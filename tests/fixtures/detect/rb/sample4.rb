@@ -0,0 +1,12 @@
+require "json"
+require "net/http"
+
+uri = URI("https://api.example.com/status")
+response = Net::HTTP.get_response(uri)
+
+if response.is_a?(Net::HTTPSuccess)
+  data = JSON.parse(response.body, symbolize_names: true)
+  puts "Status: #{data[:status]}"
+else
+  warn "Request failed with #{response.code}"
+end
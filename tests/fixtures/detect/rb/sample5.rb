@@ -0,0 +1,9 @@
+RSpec.describe Cart do
+  let(:cart) { described_class.new }
+
+  it "sums item prices" do
+    cart.add(Item.new(price: 5))
+    cart.add(Item.new(price: 7))
+    expect(cart.total).to eq(12)
+  end
+end
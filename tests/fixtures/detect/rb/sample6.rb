@@ -0,0 +1,7 @@
+module Greeter
+  def self.greet(names)
+    names.map { |n| "Hello, #{n}!" }.join("\n")
+  end
+end
+
+puts Greeter.greet(%w[Ann Bob])
@@ -0,0 +1,5 @@
+pub fn parse_line(line: &str) -> Option<(String, u32)> {
+    let (name, count) = line.split_once(':')?;
+    let count = count.trim().parse().ok()?;
+    Some((name.trim().to_owned(), count))
+}
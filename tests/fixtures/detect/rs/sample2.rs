@@ -0,0 +1,11 @@
+#[derive(Default)]
+struct Stats {
+    hits: u64,
+    misses: u64,
+}
+
+impl Stats {
+    fn ratio(&self) -> f64 {
+        self.hits as f64 / (self.hits + self.misses).max(1) as f64
+    }
+}
@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub struct Inventory {
+    items: HashMap<String, u32>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, name: &str, count: u32) {
+        *self.items.entry(name.to_string()).or_insert(0) += count;
+    }
+
+    pub fn remove(&mut self, name: &str, count: u32) -> Result<(), String> {
+        let have = self.items.get_mut(name).ok_or_else(|| format!("no {name}"))?;
+        *have = have.checked_sub(count).ok_or("not enough")?;
+        Ok(())
+    }
+}
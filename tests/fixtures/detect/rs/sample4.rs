@@ -0,0 +1,12 @@
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:8080").await?;
+    loop {
+        let (socket, addr) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(e) = handle(socket).await {
+                eprintln!("{addr}: {e}");
+            }
+        });
+    }
+}
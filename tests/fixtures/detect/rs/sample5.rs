@@ -0,0 +1,14 @@
+fn parse_line(line: &str) -> Option<(&str, i64)> {
+    let (name, value) = line.split_once('=')?;
+    Some((name.trim(), value.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses() {
+        assert_eq!(parse_line("a = 3"), Some(("a", 3)));
+    }
+}
@@ -0,0 +1,6 @@
+object Hello {
+  def main(args: Array[String]): Unit = {
+    val names = Seq("a", "b", "c")
+    names.foreach(n => println(s"Hello $n"))
+  }
+}
@@ -0,0 +1,6 @@
+case class Account(id: String, balance: BigDecimal) {
+  def deposit(amount: BigDecimal): Account = copy(balance = balance + amount)
+}
+
+val accounts = List(Account("1", 10), Account("2", 20))
+val total = accounts.map(_.balance).sum
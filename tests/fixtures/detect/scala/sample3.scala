@@ -0,0 +1,15 @@
+case class Order(id: Long, items: List[Item], status: Status)
+
+sealed trait Status
+case object Open extends Status
+case object Shipped extends Status
+
+object Orders {
+  def totals(orders: Seq[Order]): Map[Long, BigDecimal] =
+    orders.map(o => o.id -> o.items.map(_.price).sum).toMap
+
+  def open(orders: Seq[Order]): Seq[Order] = orders.filter {
+    case Order(_, _, Open) => true
+    case _                 => false
+  }
+}
@@ -0,0 +1,9 @@
+val spark = SparkSession.builder.appName("wordcount").getOrCreate()
+import spark.implicits._
+
+val counts = spark.read.textFile("input.txt")
+  .flatMap(_.split("\\s+"))
+  .groupByKey(identity)
+  .count()
+
+counts.show()
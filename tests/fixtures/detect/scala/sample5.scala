@@ -0,0 +1,8 @@
+def fib(n: Int): BigInt = {
+  @annotation.tailrec
+  def loop(i: Int, a: BigInt, b: BigInt): BigInt =
+    if (i == 0) a else loop(i - 1, b, a + b)
+  loop(n, 0, 1)
+}
+
+implicit val ordering: Ordering[Person] = Ordering.by(_.age)
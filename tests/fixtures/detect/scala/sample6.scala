@@ -0,0 +1,9 @@
+import scala.concurrent.Future
+import scala.concurrent.ExecutionContext.Implicits.global
+
+def fetchAll(ids: List[Int]): Future[List[User]] =
+  Future.sequence(ids.map(fetchUser))
+
+fetchAll(List(1, 2, 3)).foreach { users =>
+  users.foreach(u => println(u.name))
+}
@@ -0,0 +1,5 @@
+#!/bin/sh
+for f in *.jpg; do
+    convert "$f" -resize 50% "small_$f"
+done
+echo "done"
@@ -0,0 +1,6 @@
+if ! command -v jq >/dev/null 2>&1; then
+    echo "jq is required" >&2
+    exit 1
+fi
+VERSION=$(jq -r .version package.json)
+git tag "v$VERSION" && git push --tags
@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+BACKUP_DIR="/var/backups/db"
+STAMP="$(date +%Y%m%d-%H%M%S)"
+
+mkdir -p "$BACKUP_DIR"
+pg_dump "$DATABASE_URL" | gzip > "$BACKUP_DIR/db-$STAMP.sql.gz"
+find "$BACKUP_DIR" -name '*.sql.gz' -mtime +14 -delete
+echo "backup written to $BACKUP_DIR/db-$STAMP.sql.gz"
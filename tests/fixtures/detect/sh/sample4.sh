@@ -0,0 +1,4 @@
+for f in *.png; do
+  [ -e "$f" ] || continue
+  convert "$f" -resize 50% "thumbs/${f%.png}.jpg"
+done
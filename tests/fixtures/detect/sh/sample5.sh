@@ -0,0 +1,10 @@
+if ! command -v docker >/dev/null 2>&1; then
+  echo "docker is required" >&2
+  exit 1
+fi
+
+case "$1" in
+  start) docker compose up -d ;;
+  stop) docker compose down ;;
+  *) echo "usage: $0 {start|stop}"; exit 2 ;;
+esac
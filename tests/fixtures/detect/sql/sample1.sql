@@ -0,0 +1,5 @@
+SELECT p.title, c.name
+FROM posts p
+JOIN categories c ON c.id = p.category_id
+WHERE p.published = 1
+ORDER BY p.created_at DESC;
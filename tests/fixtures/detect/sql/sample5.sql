@@ -0,0 +1,2 @@
+update users set last_login = now() where id = 42;
+insert into audit_log (user_id, action) values (42, 'login');
@@ -0,0 +1,5 @@
+let formatter = DateFormatter()
+formatter.dateStyle = .medium
+let label = UILabel()
+label.text = formatter.string(from: Date())
+view.addSubview(label)
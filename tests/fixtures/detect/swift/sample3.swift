@@ -0,0 +1,14 @@
+import SwiftUI
+
+struct ContentView: View {
+    @State private var name = ""
+
+    var body: some View {
+        VStack(spacing: 12) {
+            TextField("Name", text: $name)
+                .textFieldStyle(.roundedBorder)
+            Text("Hello, \(name.isEmpty ? "stranger" : name)!")
+        }
+        .padding()
+    }
+}
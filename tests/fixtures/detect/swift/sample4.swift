@@ -0,0 +1,13 @@
+enum NetworkError: Error {
+    case badStatus(Int)
+    case decoding(Error)
+}
+
+func fetchUser(id: Int) async throws -> User {
+    let url = URL(string: "https://api.example.com/users/\(id)")!
+    let (data, response) = try await URLSession.shared.data(from: url)
+    guard let http = response as? HTTPURLResponse, http.statusCode == 200 else {
+        throw NetworkError.badStatus((response as? HTTPURLResponse)?.statusCode ?? -1)
+    }
+    return try JSONDecoder().decode(User.self, from: data)
+}
@@ -0,0 +1,9 @@
+extension Array where Element: Hashable {
+    func uniqued() -> [Element] {
+        var seen = Set<Element>()
+        return filter { seen.insert($0).inserted }
+    }
+}
+
+let names = ["a", "b", "a", "c"]
+print(names.uniqued())
@@ -0,0 +1,8 @@
+variable "instance_type" {
+  type    = string
+  default = "t3.small"
+}
+
+output "public_ip" {
+  value = aws_instance.web.public_ip
+}
@@ -0,0 +1,16 @@
+terraform {
+  backend "s3" {
+    bucket = "acme-terraform-state"
+    key    = "network/terraform.tfstate"
+    region = "us-east-2"
+  }
+}
+
+variable "environment" {
+  description = "Deployment stage"
+  type        = string
+  validation {
+    condition     = contains(["dev", "prod"], var.environment)
+    error_message = "Must be dev or prod."
+  }
+}
@@ -0,0 +1,12 @@
+module "bucket" {
+  source = "./modules/static-site"
+
+  domain_name = "docs.acme.io"
+  index_page  = "index.html"
+  enable_cdn  = true
+}
+
+output "site_url" {
+  value       = module.bucket.website_endpoint
+  description = "Public URL of the docs site"
+}
@@ -0,0 +1,11 @@
+resource "aws_s3_bucket" "logs" {
+  bucket = "${var.prefix}-logs"
+  tags   = local.common_tags
+}
+
+data "aws_iam_policy_document" "logs" {
+  statement {
+    actions   = ["s3:PutObject"]
+    resources = ["${aws_s3_bucket.logs.arn}/*"]
+  }
+}
@@ -0,0 +1,13 @@
+locals {
+  common_tags = {
+    Project     = "billing"
+    Environment = var.environment
+  }
+}
+
+resource "aws_instance" "app" {
+  ami           = data.aws_ami.ubuntu.id
+  instance_type = "t3.micro"
+  count         = var.instance_count
+  tags          = local.common_tags
+}
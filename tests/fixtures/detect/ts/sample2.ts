@@ -0,0 +1,5 @@
+const cache: Map<string, number> = new Map();
+function memo(key: string, compute: () => number): number {
+  if (!cache.has(key)) cache.set(key, compute());
+  return cache.get(key)!;
+}
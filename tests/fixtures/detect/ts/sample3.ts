@@ -0,0 +1,13 @@
+interface ApiResponse<T> {
+  data: T;
+  error?: string;
+}
+
+export async function getJson<T>(url: string): Promise<T> {
+  const res = await fetch(url);
+  const body: ApiResponse<T> = await res.json();
+  if (body.error) {
+    throw new Error(body.error);
+  }
+  return body.data;
+}
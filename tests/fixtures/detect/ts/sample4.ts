@@ -0,0 +1,14 @@
+@Injectable({ providedIn: 'root' })
+export class CartService {
+  private items: CartItem[] = [];
+
+  constructor(private http: HttpClient) {}
+
+  add(item: CartItem): void {
+    this.items.push(item);
+  }
+
+  total(): number {
+    return this.items.reduce((sum, i) => sum + i.price * i.qty, 0);
+  }
+}
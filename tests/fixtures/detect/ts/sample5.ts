@@ -0,0 +1,9 @@
+type Status = 'idle' | 'loading' | 'done';
+
+const labels: Record<Status, string> = {
+  idle: 'Waiting',
+  loading: 'Loading…',
+  done: 'Finished',
+};
+
+export const label = (s: Status): string => labels[s];
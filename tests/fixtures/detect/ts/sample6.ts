@@ -0,0 +1,13 @@
+import { Router } from 'express';
+
+const router = Router();
+
+router.get('/health', (_req, res) => {
+  res.json({ ok: true, uptime: process.uptime() });
+});
+
+export function paginate<T>(items: T[], page: number, size = 20): T[] {
+  return items.slice((page - 1) * size, page * size);
+}
+
+export default router;
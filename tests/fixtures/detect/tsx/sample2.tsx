@@ -0,0 +1,10 @@
+type Props = { title: string; children?: React.ReactNode };
+
+export function Card({ title, children }: Props) {
+  return (
+    <section className="card">
+      <h2>{title}</h2>
+      {children}
+    </section>
+  );
+}
@@ -0,0 +1,13 @@
+import React from 'react';
+
+type ButtonProps = {
+  label: string;
+  onClick: () => void;
+  disabled?: boolean;
+};
+
+export const Button: React.FC<ButtonProps> = ({ label, onClick, disabled = false }) => (
+  <button className="btn" onClick={onClick} disabled={disabled}>
+    {label}
+  </button>
+);
@@ -0,0 +1,13 @@
+export default function UserList({ users }: { users: User[] }) {
+  const [selected, setSelected] = useState<number | null>(null);
+
+  return (
+    <ul>
+      {users.map((u) => (
+        <li key={u.id} onClick={() => setSelected(u.id)} className={u.id === selected ? 'active' : ''}>
+          {u.name}
+        </li>
+      ))}
+    </ul>
+  );
+}
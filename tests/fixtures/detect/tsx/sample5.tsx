@@ -0,0 +1,13 @@
+interface LayoutProps {
+  children: React.ReactNode;
+  title?: string;
+}
+
+export default function Layout({ children, title = 'Dashboard' }: LayoutProps) {
+  return (
+    <main>
+      <h1>{title}</h1>
+      {children}
+    </main>
+  );
+}
@@ -0,0 +1,10 @@
+const std = @import("std");
+
+pub fn main() void {
+    var sum: u64 = 0;
+    var i: u64 = 1;
+    while (i <= 100) : (i += 1) {
+        sum += i;
+    }
+    std.debug.print("{d}\n", .{sum});
+}
@@ -0,0 +1,7 @@
+fn max(comptime T: type, a: T, b: T) T {
+    return if (a > b) a else b;
+}
+
+test "max" {
+    try std.testing.expect(max(u8, 3, 7) == 7);
+}
@@ -0,0 +1,15 @@
+fn fibonacci(n: u32) u64 {
+    var a: u64 = 0;
+    var b: u64 = 1;
+    var i: u32 = 0;
+    while (i < n) : (i += 1) {
+        const next = a + b;
+        a = b;
+        b = next;
+    }
+    return a;
+}
+
+test "fibonacci" {
+    try std.testing.expectEqual(@as(u64, 55), fibonacci(10));
+}
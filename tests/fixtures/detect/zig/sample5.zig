@@ -0,0 +1,12 @@
+pub const Vec2 = struct {
+    x: f32,
+    y: f32,
+
+    pub fn add(self: Vec2, other: Vec2) Vec2 {
+        return .{ .x = self.x + other.x, .y = self.y + other.y };
+    }
+
+    pub fn length(self: Vec2) f32 {
+        return @sqrt(self.x * self.x + self.y * self.y);
+    }
+};